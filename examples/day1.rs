@@ -1,10 +1,12 @@
-use std::{fs, path::Path};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+};
 
 use anyhow::Error;
-use aoc2024::Args;
+use aoc2024::{locations::LocationLists, Args};
 use clap::Parser;
-use itertools::Itertools;
-use nom::{character::complete::*, combinator::*, multi::*, sequence::*, *};
+use debug_print::debug_println;
 
 const TEST_INPUT: &str = "3   4
 4   3
@@ -13,67 +15,30 @@ const TEST_INPUT: &str = "3   4
 3   9
 3   3";
 
-#[derive(Debug)]
-struct Data {
-    col1: Vec<i32>,
-    col2: Vec<i32>,
-}
-
-fn parse_data(i: &str) -> IResult<&str, Data> {
-    let parse_line = tuple((
-        map_res(digit1, str::parse),
-        multispace1,
-        map_res(digit1, str::parse),
-    ));
-    let mut parse_problem = many1(terminated(parse_line, multispace0));
-    let (i, problems) = parse_problem(i)?;
-
-    let col1 = problems.iter().map(|(x, _, _)| x).cloned().collect_vec();
-    let col2 = problems.iter().map(|(_, _, x)| x).cloned().collect_vec();
-
-    let data = Data { col1, col2 };
-    Ok((i, data))
-}
-
-fn read_data() -> Result<Data, Error> {
+fn read_data() -> Result<LocationLists, Error> {
     let args = Args::parse();
 
-    let contents = args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
-        let file = Path::new(&input);
-        fs::read_to_string(file)
-    })?;
+    let reader: Box<dyn BufRead> = match args.file {
+        Some(input) => Box::new(BufReader::new(File::open(input)?)),
+        None => Box::new(TEST_INPUT.as_bytes()),
+    };
 
-    let data = parse_data(&contents);
-    let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
-    assert!(data.0 == "");
-    Ok(data.1)
+    Ok(LocationLists::read(reader)?)
 }
 
 fn main() -> Result<(), Error> {
-    let mut data = read_data()?;
-
-    data.col1.sort_by(|a, b| a.cmp(b));
-    data.col2.sort_by(|a, b| a.cmp(b));
+    let data = read_data()?;
 
-    let result = data
-        .col1
-        .iter()
-        .zip_eq(data.col2.iter())
-        .map(|(a, b)| (a - b).abs())
-        .sum::<i32>();
+    debug_println!("pairs = {:?}", data.pairs().collect::<Vec<_>>());
 
-    println!("{}", result);
+    let report = data.reconcile();
 
-    // part 2
+    debug_println!("{:?}", report);
 
-    let counts = data.col2.iter().counts();
+    println!("{}", report.distance);
 
-    let result = data
-        .col1
-        .iter()
-        .map(|&x| x as usize * counts.get(&x).map_or(0, |x| *x))
-        .sum::<usize>();
+    // part 2
 
-    println!("{}", result);
+    println!("{}", report.similarity);
     Ok(())
 }
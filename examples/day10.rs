@@ -1,8 +1,8 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, trails, Args};
 use clap::Parser;
 use debug_print::debug_println;
 use itertools::Itertools;
@@ -68,85 +68,29 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl Data {
-    fn adjacent_to(&self, (r, c): (i32, i32), filter: impl Fn(u32) -> bool) -> Vec<(i32, i32)> {
-        let mut adjs = Vec::new();
-
-        macro_rules! check_and_add {
-            ($dr:expr, $dc:expr) => {
-                let pos = (r + $dr, c + $dc);
-                if self.data.get(&pos).filter(|&v| filter(*v)).is_some() {
-                    adjs.push(pos)
-                }
-            };
-        }
-
-        check_and_add!(-1, 0);
-        check_and_add!(1, 0);
-        check_and_add!(0, -1);
-        check_and_add!(0, 1);
-        adjs
-    }
-
-    fn find_trailheads(&self) -> Vec<&(i32, i32)> {
-        self.data
-            .iter()
-            .filter_map(|(k, v)| if *v == 0 { Some(k) } else { None })
-            .collect()
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let mut count = 0;
-    let trailheads = data.find_trailheads();
-    for trailhead in trailheads {
-        dp!(trailhead);
-
-        let mut endings = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back((trailhead.clone(), 1));
-
-        while let Some((pos, next)) = queue.pop_front() {
-            for adj in data.adjacent_to(pos, |v| v == next) {
-                if next == 9 {
-                    endings.insert(adj);
-                } else {
-                    queue.push_back((adj, next + 1));
-                }
-            }
-        }
+    let grid = (0..data.height as i32)
+        .map(|r| {
+            (0..data.width as i32)
+                .map(|c| data.data[&(r, c)])
+                .collect_vec()
+        })
+        .collect_vec();
+    let trailheads = trails::analyse(&grid, |a, b| b == a + 1, |v| v == 0, |v| v == 9).unwrap();
 
-        dp!(endings);
+    dp!(trailheads);
 
-        count += endings.len();
-    }
+    let count = trailheads.iter().map(|t| t.sinks.len()).sum::<usize>();
 
     println!("{count}");
 
     // part 2
 
-    let mut count = 0;
-    let trailheads = data.find_trailheads();
-    for trailhead in trailheads {
-        dp!(trailhead);
-
-        let mut queue = VecDeque::new();
-        queue.push_back((trailhead.clone(), 1));
-
-        while let Some((pos, next)) = queue.pop_front() {
-            for adj in data.adjacent_to(pos, |v| v == next) {
-                if next == 9 {
-                    count += 1;
-                } else {
-                    queue.push_back((adj, next + 1));
-                }
-            }
-        }
-    }
+    let count = trailheads.iter().map(|t| t.paths).sum::<u64>();
 
     println!("{count}");
 
@@ -1,17 +1,28 @@
-use std::{
-    collections::{HashMap, VecDeque},
-    fs,
-    path::Path,
-};
+use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{
+    dp,
+    stones::{RuleSet, Stones},
+    Args,
+};
 use clap::Parser;
 use debug_print::debug_println;
 use nom::{branch::*, character::complete::*, combinator::*, multi::*, sequence::*, *};
+use num::BigUint;
 
 const TEST_INPUT: &str = "125 17";
 
+#[derive(Parser, Debug)]
+struct BlinkArgs {
+    #[command(flatten)]
+    args: Args,
+
+    /// Also print the exact number of stones after this many blinks
+    #[arg(long)]
+    blinks: Option<usize>,
+}
+
 #[derive(Debug)]
 struct Data {
     data: Vec<u64>,
@@ -28,32 +39,7 @@ fn parse_data(i: &str) -> IResult<&str, Data> {
     Ok((i, data))
 }
 
-fn even_digits(n: u64) -> bool {
-    n.to_string().len() % 2 == 0
-}
-
-fn split_even(n: u64) -> Vec<u64> {
-    let s = n.to_string();
-    let split = s.len() / 2;
-    vec![
-        s[0..split].parse().unwrap(),
-        s[split..s.len()].parse().unwrap(),
-    ]
-}
-
-fn run_rules(n: u64) -> Vec<u64> {
-    if n == 0 {
-        vec![1]
-    } else if even_digits(n) {
-        split_even(n)
-    } else {
-        vec![n * 2024]
-    }
-}
-
-fn read_data() -> Result<Data, Error> {
-    let args = Args::parse();
-
+fn read_data(args: Args) -> Result<Data, Error> {
     let contents = args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
         let file = Path::new(&input);
         fs::read_to_string(file)
@@ -65,46 +51,28 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn counts_for(n: u64, blinks: i32, memo: &mut HashMap<(u64, i32), u64>) -> u64 {
-    if blinks == 0 {
-        1
-    } else {
-        let key = (n, blinks);
-        if let Some(c) = memo.get(&key) {
-            *c
-        } else {
-            let mut c = 0;
-            for subn in run_rules(n) {
-                c += counts_for(subn, blinks - 1, memo);
-            }
-            memo.insert(key, c);
-            c
-        }
-    }
-}
-
 fn main() -> Result<(), Error> {
-    let data = read_data()?;
+    let args = BlinkArgs::parse();
+    let data = read_data(args.args)?;
 
     dp!(data);
 
-    let mut memo = HashMap::new();
+    let rules = RuleSet::puzzle();
+    let mut stones = Stones::<u128>::new(&data.data);
 
-    let count = data
-        .data
-        .iter()
-        .map(|n| counts_for(*n, 25, &mut memo))
-        .sum::<u64>();
+    let mut distinct = stones.blink_many(&rules, 25)?;
+    println!("{}", stones.total());
 
-    println!("{count}");
+    distinct.extend(stones.blink_many(&rules, 50)?);
+    println!("{}", stones.total());
 
-    let count = data
-        .data
-        .into_iter()
-        .map(|n| counts_for(n, 75, &mut memo))
-        .sum::<u64>();
+    dp!(distinct);
 
-    println!("{count}");
+    if let Some(blinks) = args.blinks {
+        let mut stones = Stones::<BigUint>::new(&data.data);
+        stones.blink_many(&rules, blinks)?;
+        println!("{}", stones.total());
+    }
 
     Ok(())
 }
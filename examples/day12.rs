@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, regions::RegionMap, Args};
 use character::complete::{alpha1, multispace0};
 use clap::Parser;
 use debug_print::debug_println;
@@ -7,11 +7,7 @@ use itertools::Itertools;
 use multi::many1;
 use nom::*;
 use sequence::terminated;
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    fs,
-    path::Path,
-};
+use std::{collections::HashMap, fs, path::Path};
 
 const TEST_INPUT: &str = "RRRRIICCFF
 RRRRIICCCF
@@ -68,187 +64,35 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl Data {
-    fn find_adj(&self, (r, c): (i32, i32), filter: impl Fn(char) -> bool) -> Vec<(i32, i32)> {
-        let mut adjs = Vec::new();
-
-        macro_rules! check_and_add {
-            ($dr:expr, $dc:expr) => {
-                let pos = (r + $dr, c + $dc);
-                if self.data.get(&pos).filter(|&v| filter(*v)).is_some() {
-                    adjs.push(pos)
-                }
-            };
-        }
-
-        check_and_add!(-1, 0);
-        check_and_add!(1, 0);
-        check_and_add!(0, -1);
-        check_and_add!(0, 1);
-        adjs
-    }
-
-    fn find_all_adj(&self, pos: (i32, i32), filter: impl Fn(char) -> bool) -> HashSet<(i32, i32)> {
-        let mut queue = VecDeque::new();
-        let mut result = HashSet::new();
-
-        queue.push_front(pos);
-        while let Some(v) = queue.pop_front() {
-            for adj in self.find_adj(v, &filter) {
-                if !result.contains(&adj) {
-                    result.insert(adj);
-                    queue.push_back(adj);
-                }
-            }
-        }
-
-        result
-    }
-}
-
-fn identify_regions(data: &Data) -> HashMap<String, Vec<(i32, i32)>> {
-    let mut h = HashMap::new();
-    let mut work = (0..data.width)
-        .flat_map(|col| {
-            (0..data.height)
-                .map(|row| (row as i32, col as i32))
-                .collect_vec()
-        })
-        .collect_vec();
-
-    let mut count = 0;
-    while let Some(orig_pos) = work.pop() {
-        count += 1;
-        let val = *data.data.get(&orig_pos).unwrap();
-        let name = {
-            let mut s = String::from(val);
-            s.push_str(&count.to_string());
-            s
-        };
-        dp!(name);
-        dp!(orig_pos);
-        for p in data.find_all_adj(orig_pos, |c| c == val) {
-            if let Some(idx) = work.iter().position(|x| *x == p) {
-                dp!(p);
-                work.swap_remove(idx);
-
-                h.entry(name.clone()).or_insert_with(Vec::new).push(p);
-            }
-        }
-        h.entry(name).or_insert_with(Vec::new).push(orig_pos);
-    }
-    h
-}
-
-fn calculate_cost(regions: &HashMap<String, Vec<(i32, i32)>>, data: &Data) -> u64 {
-    let mut total_cost = 0;
-    for (name, region) in regions.iter() {
-        let mut perimeter = 0;
-        for pos in region.iter() {
-            macro_rules! on_edge {
-                ($dr:expr, $dc:expr) => {{
-                    let pos = (pos.0 + $dr, pos.1 + $dc);
-                    !region.contains(&pos) || data.data.get(&pos).is_none()
-                }};
-            }
-            if on_edge!(0, 1) {
-                perimeter += 1;
-            }
-            if on_edge!(0, -1) {
-                perimeter += 1;
-            }
-            if on_edge!(1, 0) {
-                perimeter += 1;
-            }
-            if on_edge!(-1, 0) {
-                perimeter += 1;
-            }
-        }
-        let area = region.len();
-        total_cost += perimeter as u64 * area as u64;
-        dp!(name);
-        dp!(area);
-        dp!(perimeter);
-    }
-
-    total_cost
-}
-
-// this is pretty inefficient but did work to solve the problem
-fn calculate_cost_by_edge(regions: &HashMap<String, Vec<(i32, i32)>>, data: &Data) -> u64 {
-    let mut total_cost = 0;
-    for (name, region) in regions.iter() {
-        let mut edge_count = 0;
-        // do N, S, E, W separately
-        for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let mut perimeter_tiles = HashSet::new();
-            for pos in region.iter() {
-                macro_rules! add_perimeter_tile {
-                    ($dr:expr, $dc:expr) => {{
-                        let pos = (pos.0 + $dr, pos.1 + $dc);
-                        if !region.contains(&pos) || data.data.get(&pos).is_none() {
-                            perimeter_tiles.insert(pos);
-                        }
-                    }};
-                }
-                add_perimeter_tile!(dr, dc);
-            }
-
-            // group perimeter tiles by adjacencies, can reuse existing code above for this
-            let perimeter_region = {
-                let mut map = HashMap::new();
-                for c in 0..data.width + 2 {
-                    for r in 0..data.height + 2 {
-                        let ci = c as i32;
-                        let ri = r as i32;
-                        let region_char = if perimeter_tiles.contains(&(ri - 1, ci - 1)) {
-                            'A'
-                        } else {
-                            'O'
-                        };
-                        map.insert((ri, ci), region_char);
-                    }
-                }
-                Data {
-                    data: map,
-                    width: data.width + 2,
-                    height: data.height + 2,
-                }
-            };
-
-            let perimeter_regions = identify_regions(&perimeter_region);
-
-            // find those regions starting with 'A'
-            let perimeter = perimeter_regions
-                .keys()
-                .filter(|k| k.starts_with('A'))
-                .count();
-            edge_count += perimeter;
-        }
-
-        let area = region.len();
-        total_cost += edge_count as u64 * area as u64;
-        dp!(name);
-        dp!(area);
-    }
-
-    total_cost
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let regions = identify_regions(&data);
+    let grid = (0..data.height as i32)
+        .map(|r| {
+            (0..data.width as i32)
+                .map(|c| data.data[&(r, c)])
+                .collect_vec()
+        })
+        .collect_vec();
+    let regions = RegionMap::new(&grid);
 
-    dp!(regions);
+    debug_println!("{}", regions.render_colored());
 
-    let result = calculate_cost(&regions, &data);
+    let result = regions
+        .regions()
+        .iter()
+        .map(|r| (r.area() * r.perimeter) as u64)
+        .sum::<u64>();
 
     println!("{result}");
 
-    let result = calculate_cost_by_edge(&regions, &data);
+    let result = regions
+        .regions()
+        .iter()
+        .map(|r| (r.area() * r.sides) as u64)
+        .sum::<u64>();
 
     println!("{result}");
 
@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, linear, Args};
 use bytes::complete::tag;
 use character::complete::{digit1, multispace0};
 use clap::Parser;
@@ -7,7 +7,7 @@ use combinator::map_res;
 use debug_print::debug_println;
 use multi::many1;
 use nom::*;
-use num::Integer;
+use num::BigInt;
 use sequence::{preceded, separated_pair, terminated, tuple};
 use std::{fs, path::Path};
 
@@ -86,35 +86,26 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn solve_game(game: &Game) -> Option<((i32, i32), i32)> {
-    let mut best = None;
-    for a in 0..100 {
-        let b_and_rem = (game.prize_loc.0 - a * game.button_a.0).div_rem(&game.button_b.0);
-        if b_and_rem.1 != 0 {
-            continue;
-        }
-        let b = b_and_rem.0;
+type Solution = ((i32, i32), i32);
 
-        let pos = (
-            a * game.button_a.0 + b * game.button_b.0,
-            a * game.button_a.1 + b * game.button_b.1,
-        );
+fn solve_game(game: &Game) -> Result<Option<Solution>, linear::Unsupported> {
+    let (ax, ay) = game.button_a;
+    let (bx, by) = game.button_b;
+    let (px, py) = game.prize_loc;
 
-        if pos != game.prize_loc {
-            continue;
-        }
+    let a = linear::matrix(&[&[ax.into(), bx.into()], &[ay.into(), by.into()]]);
+    let b = [px.into(), py.into()];
+    let cost = [3.into(), 1.into()];
+    let Some((presses, cost)) = linear::min_cost_solution(&a, &b, &cost, Some(&BigInt::from(100)))?
+    else {
+        return Ok(None);
+    };
 
-        // check if best
-        let cost = a * 3 + b;
-        if let Some((_, old_cost)) = best {
-            if cost < old_cost {
-                best = Some(((a, b), cost));
-            }
-        } else {
-            best = Some(((a, b), cost));
-        }
-    }
-    best
+    let to_int = |v: &BigInt| v.try_into().unwrap();
+    Ok(Some((
+        (to_int(&presses[0]), to_int(&presses[1])),
+        to_int(&cost),
+    )))
 }
 
 fn main() -> Result<(), Error> {
@@ -125,7 +116,7 @@ fn main() -> Result<(), Error> {
     let mut tokens = 0;
     for game in data.data {
         dp!(game);
-        let solution = solve_game(&game);
+        let solution = solve_game(&game)?;
         dp!(solution);
 
         if let Some((_, cost)) = solution {
@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, linear, Args};
 use bytes::complete::tag;
 use character::complete::{digit1, multispace0};
 use clap::Parser;
@@ -7,7 +7,7 @@ use combinator::map_res;
 use debug_print::debug_println;
 use multi::many1;
 use nom::*;
-use num::Integer;
+use num::BigInt;
 use sequence::{preceded, separated_pair, terminated, tuple};
 use std::{fs, path::Path};
 
@@ -86,30 +86,25 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn solve_game(game: &Game) -> Option<((i64, i64), i64)> {
+type Solution = ((i64, i64), i64);
+
+fn solve_game(game: &Game) -> Result<Option<Solution>, linear::Unsupported> {
     let (ax, ay) = game.button_a;
     let (bx, by) = game.button_b;
     let (px, py) = game.prize_loc;
-    let bnum = ax * py - ay * px;
-    let bden = ax * by - ay * bx;
-    let b_and_rem = bnum.div_rem(&bden);
-
-    if b_and_rem.1 != 0 {
-        // no solution
-        None
-    } else {
-        let b = b_and_rem.0;
-        let anum = px - b * bx;
-        let a_and_rem = anum.div_rem(&ax);
-        if a_and_rem.1 != 0 {
-            // again no solution
-            None
-        } else {
-            let a = a_and_rem.0;
-            let cost = a * 3 + b;
-            Some(((a, b), cost))
-        }
-    }
+
+    let a = linear::matrix(&[&[ax, bx], &[ay, by]]);
+    let b = [px.into(), py.into()];
+    let cost = [3.into(), 1.into()];
+    let Some((presses, cost)) = linear::min_cost_solution(&a, &b, &cost, None)? else {
+        return Ok(None);
+    };
+
+    let to_int = |v: &BigInt| v.try_into().unwrap();
+    Ok(Some((
+        (to_int(&presses[0]), to_int(&presses[1])),
+        to_int(&cost),
+    )))
 }
 
 fn main() -> Result<(), Error> {
@@ -120,7 +115,7 @@ fn main() -> Result<(), Error> {
     let mut tokens = 0;
     for game in data.data {
         dp!(game);
-        let solution = solve_game(&game);
+        let solution = solve_game(&game)?;
         dp!(solution);
 
         if let Some((_, cost)) = solution {
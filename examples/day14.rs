@@ -1,5 +1,9 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{
+    dp,
+    torus::{Particle, Torus},
+    Args,
+};
 use bytes::complete::tag;
 use character::complete::{char, digit1, multispace0, multispace1};
 use clap::Parser;
@@ -8,7 +12,6 @@ use debug_print::debug_println;
 use itertools::Itertools;
 use multi::many1;
 use nom::*;
-use num::Integer;
 use sequence::{preceded, separated_pair, terminated, tuple};
 use std::{fs, path::Path};
 
@@ -25,15 +28,9 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
 
-#[derive(Debug, Clone, Copy)]
-struct Robot {
-    pos: (i32, i32),
-    vel: (i32, i32),
-}
-
 #[derive(Debug)]
 struct Data {
-    data: Vec<Robot>,
+    data: Vec<Particle>,
     width: usize,
     height: usize,
 }
@@ -43,9 +40,9 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
         map_res(
             tuple((opt(char('-')), digit1)),
             |(sign, num): (Option<char>, &str)| {
-                let n: i32 = num.parse()?;
+                let n: i64 = num.parse()?;
                 if sign.is_some() {
-                    Ok::<i32, Error>(-n)
+                    Ok::<i64, Error>(-n)
                 } else {
                     Ok(n)
                 }
@@ -64,7 +61,7 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
 
     let data = problems
         .into_iter()
-        .map(|(pos, vel)| Robot { pos, vel })
+        .map(|(pos, vel)| Particle { pos, vel })
         .collect();
 
     let data = Data {
@@ -94,37 +91,30 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl Robot {
-    fn step(&mut self, width: usize, height: usize) {
-        self.pos.0 = (self.pos.0 + self.vel.0).mod_floor(&(width as i32));
-        self.pos.1 = (self.pos.1 + self.vel.1).mod_floor(&(height as i32));
-    }
-
-    fn quadrant(&self, width: usize, height: usize) -> (Option<usize>, Option<usize>) {
-        let x = if (self.pos.0 as usize) < width / 2 {
-            Some(0)
-        } else if (self.pos.0 as usize) > width / 2 {
-            Some(1)
-        } else {
-            None
-        };
-
-        let y = if (self.pos.1 as usize) < height / 2 {
-            Some(0)
-        } else if (self.pos.1 as usize) > height / 2 {
-            Some(1)
-        } else {
-            None
-        };
-
-        (x, y)
-    }
+fn quadrant(pos: (i64, i64), width: usize, height: usize) -> (Option<usize>, Option<usize>) {
+    let x = if (pos.0 as usize) < width / 2 {
+        Some(0)
+    } else if (pos.0 as usize) > width / 2 {
+        Some(1)
+    } else {
+        None
+    };
+
+    let y = if (pos.1 as usize) < height / 2 {
+        Some(0)
+    } else if (pos.1 as usize) > height / 2 {
+        Some(1)
+    } else {
+        None
+    };
+
+    (x, y)
 }
 
-fn calc_score(robots: &Vec<Robot>, width: usize, height: usize) -> u64 {
-    let quads = robots
+fn calc_score(positions: &[(i64, i64)], width: usize, height: usize) -> u64 {
+    let quads = positions
         .iter()
-        .map(|r| r.quadrant(width, height))
+        .map(|&p| quadrant(p, width, height))
         .filter(|(x, y)| x.is_some() && y.is_some())
         .counts();
 
@@ -138,18 +128,12 @@ fn main() -> Result<(), Error> {
 
     dp!(data);
 
-    let mut robots = data.data.clone();
-    for r in robots.iter_mut() {
-        dp!(r);
-        for _step in 0..100 {
-            r.step(data.width, data.height);
-        }
-        dp!(r);
-    }
+    let torus = Torus::new(data.width as i64, data.height as i64);
+    let positions = torus.positions_at(&data.data, 100);
 
-    dp!(robots);
+    dp!(positions);
 
-    let score = calc_score(&robots, data.width, data.height);
+    let score = calc_score(&positions, data.width, data.height);
 
     println!("{score}");
 
@@ -1,16 +1,23 @@
 use anyhow::Error;
-use aoc2024::{dp, read_line, Args};
+use aoc2024::{
+    bitmap::Bitmap,
+    dp,
+    torus::{Particle, Torus},
+    Args,
+};
 use bytes::complete::tag;
 use character::complete::{char, digit1, multispace0, multispace1};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use combinator::{map_res, opt};
 use debug_print::debug_println;
 use itertools::Itertools;
 use multi::many1;
 use nom::*;
-use num::Integer;
 use sequence::{preceded, separated_pair, terminated, tuple};
-use std::{fs, path::Path};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 const TEST_INPUT: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -25,15 +32,63 @@ p=7,3 v=-1,2
 p=2,4 v=2,-3
 p=9,5 v=-3,-3";
 
-#[derive(Debug, Clone, Copy)]
-struct Robot {
-    pos: (i32, i32),
-    vel: (i32, i32),
+#[derive(Debug, Copy, Clone, ValueEnum)]
+enum ImageFormat {
+    Pbm,
+    Pgm,
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Pbm => "pbm",
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Png => "png",
+        }
+    }
+
+    fn encode(&self, bitmap: &Bitmap) -> Vec<u8> {
+        match self {
+            ImageFormat::Pbm => bitmap.to_pbm(),
+            ImageFormat::Pgm => bitmap.to_pgm(),
+            ImageFormat::Png => bitmap.to_png(),
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+struct ExportArgs {
+    #[command(flatten)]
+    args: Args,
+
+    /// Write one image per step into this directory
+    #[arg(long)]
+    frames: Option<PathBuf>,
+
+    /// Write all exported steps tiled into a single image
+    #[arg(long)]
+    sheet: Option<PathBuf>,
+
+    /// First step to export
+    #[arg(long, default_value_t = 0)]
+    from: i64,
+
+    /// Step to stop exporting at (exclusive), defaults to the period
+    #[arg(long)]
+    to: Option<i64>,
+
+    #[arg(long, value_enum, default_value_t = ImageFormat::Png)]
+    format: ImageFormat,
+
+    /// Frames per row in the contact sheet
+    #[arg(long, default_value_t = 10)]
+    columns: usize,
 }
 
 #[derive(Debug)]
 struct Data {
-    data: Vec<Robot>,
+    data: Vec<Particle>,
     width: usize,
     height: usize,
 }
@@ -43,9 +98,9 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
         map_res(
             tuple((opt(char('-')), digit1)),
             |(sign, num): (Option<char>, &str)| {
-                let n: i32 = num.parse()?;
+                let n: i64 = num.parse()?;
                 if sign.is_some() {
-                    Ok::<i32, Error>(-n)
+                    Ok::<i64, Error>(-n)
                 } else {
                     Ok(n)
                 }
@@ -64,7 +119,7 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
 
     let data = problems
         .into_iter()
-        .map(|(pos, vel)| Robot { pos, vel })
+        .map(|(pos, vel)| Particle { pos, vel })
         .collect();
 
     let data = Data {
@@ -75,9 +130,7 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
     Ok((i, data))
 }
 
-fn read_data() -> Result<Data, Error> {
-    let args = Args::parse();
-
+fn read_data(args: Args) -> Result<Data, Error> {
     let mut width = 11;
     let mut height = 7;
 
@@ -94,94 +147,12 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl Robot {
-    fn step(&mut self, width: usize, height: usize) {
-        self.pos.0 = (self.pos.0 + self.vel.0).mod_floor(&(width as i32));
-        self.pos.1 = (self.pos.1 + self.vel.1).mod_floor(&(height as i32));
-    }
-
-    fn quadrant(&self, width: usize, height: usize) -> (Option<usize>, Option<usize>) {
-        let x = if (self.pos.0 as usize) < width / 2 {
-            Some(0)
-        } else if (self.pos.0 as usize) > width / 2 {
-            Some(1)
-        } else {
-            None
-        };
-
-        let y = if (self.pos.1 as usize) < height / 2 {
-            Some(0)
-        } else if (self.pos.1 as usize) > height / 2 {
-            Some(1)
-        } else {
-            None
-        };
-
-        (x, y)
-    }
-
-    // every one repeats (returns to original position) after 10403 steps
-    fn find_repetition_count(&self, width: usize, height: usize) -> Option<usize> {
-        let mut test = self.clone();
-        let mut step = 0;
-        loop {
-            step += 1;
-            test.step(width, height);
-
-            if test.pos == self.pos {
-                break;
-            }
-
-            if step > 1000000 {
-                return None;
-            }
-        }
-        Some(step)
-    }
-}
-
-fn potential_tree(robots: &Vec<Robot>, width: usize, height: usize) -> bool {
-    // originally assumed easter-egg tree would be mirrored vertically, then after that
-    // didn't work, assume trunk with points in the middle
-
-    //let partitions: (Vec<_>, Vec<_>) = robots
-    //    .iter()
-    //    .cloned()
-    //    .partition(|r| r.pos.0 < (width as i32) / 2);
-
-    //let rmap = robots.iter().map(|r| r.pos).counts();
-    //for c in 0..width / 2 - 1 {
-    //    for r in 0..height {
-    //        let p1 = (c as i32, r as i32);
-    //        let p2 = ((width - c - 1) as i32, r as i32);
-    //        let v1 = rmap.get(&p1);
-    //        let v2 = rmap.get(&p2);
-    //        match (v1, v2) {
-    //            (None, None) => (),
-    //            (None, Some(_)) => return false,
-    //            (Some(_), None) => return false,
-    //            (Some(c1), Some(c2)) if c1 != c2 => return false,
-    //            (Some(_), Some(_)) => (),
-    //        }
-    //    }
-    //}
-
-    //partitions.0.len() == partitions.1.len()
-    //true
-
-    robots
-        .iter()
-        .filter(|r| r.pos.0 >= ((width as i32) - 2) / 2 && r.pos.0 <= ((width as i32) + 2) / 2)
-        .count()
-        > 50
-}
-
-fn display_robots(robots: &Vec<Robot>, width: usize, height: usize) {
-    let rmap = robots.iter().map(|r| r.pos).counts();
+fn display_robots(positions: &[(i64, i64)], width: usize, height: usize) {
+    let rmap = positions.iter().counts();
 
     for r in 0..height {
         for c in 0..width {
-            match rmap.get(&(c as i32, r as i32)) {
+            match rmap.get(&(c as i64, r as i64)) {
                 Some(n) => print!("{n}"),
                 None => print!("."),
             };
@@ -190,36 +161,62 @@ fn display_robots(robots: &Vec<Robot>, width: usize, height: usize) {
     }
 }
 
-fn main() -> Result<(), Error> {
-    let data = read_data()?;
-
-    dp!(data);
-
-    let mut robots = data.data.clone();
-    let mut step = 0;
-    loop {
-        step += 1;
-        for r in robots.iter_mut() {
-            dp!(r);
+fn export_frames(args: &ExportArgs, data: &Data, torus: &Torus, period: i64) -> Result<(), Error> {
+    if args.frames.is_none() && args.sheet.is_none() {
+        return Ok(());
+    }
 
-            r.step(data.width, data.height);
+    if let Some(dir) = &args.frames {
+        fs::create_dir_all(dir)?;
+    }
 
-            dp!(r);
+    // frames are written as they are made, only the contact sheet needs
+    // them all at once
+    let mut sheet_frames = vec![];
+    for step in args.from..args.to.unwrap_or(period) {
+        let positions = torus.positions_at(&data.data, step);
+        let frame = Bitmap::from_points(data.width, data.height, &positions);
+        if let Some(dir) = &args.frames {
+            let path = dir.join(format!("{:05}.{}", step, args.format.extension()));
+            fs::write(path, args.format.encode(&frame))?;
         }
-        println!("{step}");
-
-        if potential_tree(&robots, data.width, data.height) {
-            println!("{step}");
-            display_robots(&robots, data.width, data.height);
-            //break;
-            read_line()?;
+        if args.sheet.is_some() {
+            sheet_frames.push(frame);
         }
+    }
 
-        if step > 10403 {
-            println!("no symmetry found");
-            break;
-        }
+    if let Some(path) = &args.sheet {
+        let sheet = Bitmap::contact_sheet(&sheet_frames, args.columns, 2);
+        fs::write(path, args.format.encode(&sheet))?;
     }
 
     Ok(())
 }
+
+fn main() -> Result<(), Error> {
+    let args = ExportArgs::parse();
+    let data = read_data(args.args.clone())?;
+
+    dp!(data);
+
+    let torus = Torus::new(data.width as i64, data.height as i64);
+    let period = torus.period(&data.data);
+    dp!(period);
+
+    export_frames(&args, &data, &torus, period)?;
+
+    let Some(step) = torus.most_clustered_time(&data.data) else {
+        println!("no tree found");
+        return Ok(());
+    };
+    let step = step % period;
+
+    display_robots(
+        &torus.positions_at(&data.data, step),
+        data.width,
+        data.height,
+    );
+    println!("{step}");
+
+    Ok(())
+}
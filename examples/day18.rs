@@ -44,9 +44,26 @@ struct Data {
     data: Vec<(i32, i32)>,
     width: usize,
     height: usize,
+    initial: usize,
 }
 
-fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
+#[derive(Parser, Debug)]
+struct GridArgs {
+    #[command(flatten)]
+    args: Args,
+
+    /// Width and height of the memory space, 7 for the example and 71
+    /// for real input if not given
+    #[arg(long)]
+    size: Option<usize>,
+
+    /// Number of bytes that have fallen before the path is checked, 12
+    /// for the example and 1024 for real input if not given
+    #[arg(long)]
+    bytes: Option<usize>,
+}
+
+fn parse_data(i: &str, width: usize, height: usize, initial: usize) -> IResult<&str, Data> {
     let parse_val = || map_res(digit1, str::parse);
 
     let parse_xy = separated_pair(parse_val(), tag(","), parse_val());
@@ -59,24 +76,25 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
         data: problems,
         width,
         height,
+        initial,
     };
     Ok((i, data))
 }
 
-fn read_data() -> Result<Data, Error> {
-    let args = Args::parse();
-
-    let mut width = 7;
-    let mut height = 7;
+fn read_data(args: GridArgs) -> Result<Data, Error> {
+    let (default_size, default_bytes) = match args.args.file {
+        None => (7, 12),
+        Some(_) => (71, 1024),
+    };
+    let size = args.size.unwrap_or(default_size);
+    let initial = args.bytes.unwrap_or(default_bytes);
 
-    let contents = args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
-        width = 71;
-        height = 71;
+    let contents = args.args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
         let file = Path::new(&input);
         fs::read_to_string(file)
     })?;
 
-    let data = parse_data(&contents, width, height);
+    let data = parse_data(&contents, size, size, initial);
     let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
     assert!(data.0.is_empty());
     Ok(data.1)
@@ -107,7 +125,7 @@ impl DijkstraInput for Map {
 }
 
 fn main() -> Result<(), Error> {
-    let data = read_data()?;
+    let data = read_data(GridArgs::parse())?;
 
     dp!(data);
 
@@ -119,21 +137,9 @@ fn main() -> Result<(), Error> {
         }
     }
 
-    // changes for example
-    let num_bytes_to_apply = if data.width == 7 {
-        12
-    } else if data.width == 71 {
-        1024
-    } else {
-        unreachable!()
-    };
-
-    data.data
-        .iter()
-        .take(num_bytes_to_apply)
-        .for_each(|&(c, r)| {
-            map.insert((c, r), false);
-        });
+    data.data.iter().take(data.initial).for_each(|&(c, r)| {
+        map.insert((c, r), false);
+    });
 
     dp!(map);
 
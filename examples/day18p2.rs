@@ -1,18 +1,14 @@
-use anyhow::Error;
-use aoc2024::{
-    dijkstra::{DijkstraConfig, DijkstraInput, DijkstraMap},
-    dp, Args,
-};
+use anyhow::{anyhow, Error};
+use aoc2024::{connectivity::first_blocking_drop, dp, Args};
 use bytes::complete::tag;
 use character::complete::{digit1, multispace0};
 use clap::Parser;
 use combinator::map_res;
 use debug_print::debug_println;
-use itertools::Itertools;
 use multi::many1;
 use nom::*;
 use sequence::{separated_pair, terminated};
-use std::{collections::HashMap, fs, path::Path};
+use std::{fs, path::Path};
 
 const TEST_INPUT: &str = "5,4
 4,2
@@ -45,9 +41,26 @@ struct Data {
     data: Vec<(i32, i32)>,
     width: usize,
     height: usize,
+    initial: usize,
 }
 
-fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
+#[derive(Parser, Debug)]
+struct GridArgs {
+    #[command(flatten)]
+    args: Args,
+
+    /// Width and height of the memory space, 7 for the example and 71
+    /// for real input if not given
+    #[arg(long)]
+    size: Option<usize>,
+
+    /// Number of bytes that have fallen before the path is checked, 12
+    /// for the example and 1024 for real input if not given
+    #[arg(long)]
+    bytes: Option<usize>,
+}
+
+fn parse_data(i: &str, width: usize, height: usize, initial: usize) -> IResult<&str, Data> {
     let parse_val = || map_res(digit1, str::parse);
 
     let parse_xy = separated_pair(parse_val(), tag(","), parse_val());
@@ -60,106 +73,49 @@ fn parse_data(i: &str, width: usize, height: usize) -> IResult<&str, Data> {
         data: problems,
         width,
         height,
+        initial,
     };
     Ok((i, data))
 }
 
-fn read_data() -> Result<Data, Error> {
-    let args = Args::parse();
-
-    let mut width = 7;
-    let mut height = 7;
+fn read_data(args: GridArgs) -> Result<Data, Error> {
+    let (default_size, default_bytes) = match args.args.file {
+        None => (7, 12),
+        Some(_) => (71, 1024),
+    };
+    let size = args.size.unwrap_or(default_size);
+    let initial = args.bytes.unwrap_or(default_bytes);
 
-    let contents = args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
-        width = 71;
-        height = 71;
+    let contents = args.args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
         let file = Path::new(&input);
         fs::read_to_string(file)
     })?;
 
-    let data = parse_data(&contents, width, height);
+    let data = parse_data(&contents, size, size, initial);
     let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
     assert!(data.0.is_empty());
     Ok(data.1)
 }
 
-#[derive(Debug)]
-struct Map<'a> {
-    data: &'a HashMap<(i32, i32), bool>,
-}
-
-impl<'a> DijkstraInput for Map<'a> {
-    type Cost = i32;
-
-    type Index = (i32, i32);
-
-    fn get_adjacent(&self, (c, r): &Self::Index) -> Vec<(Self::Cost, Self::Index)> {
-        let mut v = vec![];
-
-        for (dc, dr) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            let pos = (c + dc, r + dr);
-            if let Some(true) = self.data.get(&pos) {
-                v.push((1, pos));
-            }
-        }
-
-        v
-    }
-}
-
-fn end_reachable(map: &HashMap<(i32, i32), bool>, start: &(i32, i32), end: &(i32, i32)) -> bool {
-    let map = Map { data: map };
-
-    let mut dmap = DijkstraMap::<_, (i32, i32)>::new(&map, DijkstraConfig::default());
-
-    let costs = dmap.run((0, start.clone()));
-
-    costs.keys().contains(end)
-}
-
 fn main() -> Result<(), Error> {
-    let data = read_data()?;
+    let data = read_data(GridArgs::parse())?;
 
     dp!(data);
 
-    let mut map = HashMap::new();
-
-    for r in 0..data.height {
-        for c in 0..data.width {
-            map.insert((c as i32, r as i32), true);
-        }
-    }
-
-    // changes for example
-    let starting_num_bytes_to_apply = if data.width == 7 {
-        12
-    } else if data.width == 71 {
-        1024
-    } else {
-        unreachable!()
-    };
-
     let start = (0, 0);
     let end = (data.width as i32 - 1, data.height as i32 - 1);
 
-    let mut breaking_pos = (0, 0);
-    let mut current_drop = 0;
-    loop {
-        let pos = data.data[current_drop];
-        map.insert(pos, false);
-
-        if current_drop > starting_num_bytes_to_apply {
-            // start checking for path existance
-            let path_exists = end_reachable(&map, &start, &end);
-            if !path_exists {
-                breaking_pos = pos.clone();
-                break;
-            }
-        }
-
-        current_drop += 1;
-    }
-
+    let breaking = first_blocking_drop(
+        data.width,
+        data.height,
+        &data.data,
+        data.initial,
+        start,
+        end,
+    )?
+    .ok_or_else(|| anyhow!("no byte after the first {} cuts off the exit", data.initial))?;
+
+    let breaking_pos = data.data[breaking];
     println!("{},{}", breaking_pos.0, breaking_pos.1);
 
     Ok(())
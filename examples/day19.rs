@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, towels::PatternSet, Args};
 use bytes::complete::tag;
 use character::complete::{alpha1, multispace0, multispace1};
 use clap::Parser;
@@ -58,58 +58,21 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn is_possible(target: &str, pos: usize, stripes: &Vec<String>) -> bool {
-    let sub = &target[pos..];
-
-    for stripe in stripes.iter() {
-        if sub == stripe {
-            return true;
-        } else if stripe.len() <= sub.len() && &sub[0..stripe.len()] == stripe {
-            debug_println!("{} matches", stripe);
-            if is_possible(target, pos + stripe.len(), stripes) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-fn reduce_stripes(stripes: &Vec<String>) -> Vec<String> {
-    let mut to_remove = vec![];
-    for s in stripes.iter() {
-        for s2 in stripes.iter() {
-            if s != s2 && s2.starts_with(s) && stripes.contains(&s2[s.len()..].to_string()) {
-                to_remove.push(s2);
-            }
-        }
-    }
-
-    stripes
-        .iter()
-        .filter(|s| !to_remove.contains(s))
-        .cloned()
-        .collect()
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let reduced = reduce_stripes(&data.stripes);
-
-    debug_println!("{:?}", reduced);
+    let patterns = PatternSet::new(&data.stripes);
 
     let mut count = 0;
     for target in data.targets.iter() {
         debug_println!("examining {}", target);
-        let possible = is_possible(target, 0, &reduced);
-        debug_println!("{}, possible: {}", target, possible);
-        if possible {
+        let possible = patterns.decomposition(target);
+        debug_println!("{}, possible: {:?}", target, possible);
+        if possible.is_some() {
             count += 1;
         }
-        dp!(possible);
     }
 
     println!("{count}");
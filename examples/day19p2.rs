@@ -1,8 +1,5 @@
 use anyhow::Error;
-use aoc2024::{
-    dijkstra::{DijkstraConfig, DijkstraInput, DijkstraMap},
-    dp, Args,
-};
+use aoc2024::{dp, towels::PatternSet, Args};
 use bytes::complete::tag;
 use character::complete::{alpha1, multispace0, multispace1};
 use clap::Parser;
@@ -10,7 +7,7 @@ use debug_print::debug_println;
 use multi::{many1, separated_list1};
 use nom::*;
 use sequence::{terminated, tuple};
-use std::{collections::HashSet, fs, path::Path};
+use std::{fs, path::Path};
 
 const TEST_INPUT: &str = "r, wr, b, g, bwu, rb, gb, br
 
@@ -61,65 +58,18 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-#[derive(Debug)]
-struct TestTarget<'a> {
-    stripe_set: &'a HashSet<&'a str>,
-    target: &'a str,
-    max_len: usize,
-}
-
-impl<'a> DijkstraInput for TestTarget<'a> {
-    type Cost = i32;
-
-    type Index = usize;
-
-    fn get_adjacent(&self, x: &Self::Index) -> Vec<(Self::Cost, Self::Index)> {
-        let mut v = vec![];
-
-        let pos = *x;
-
-        for n in 1..=self.max_len {
-            let end = pos + n;
-            if end <= self.target.len() {
-                let substr = &self.target[pos..end];
-                if self.stripe_set.contains(substr) {
-                    v.push((0, end));
-                }
-            }
-        }
-
-        v
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let max_stripe_len = data.stripes.iter().map(|s| s.len()).max().unwrap();
-    let stripe_set = data.stripes.iter().map(|s| s.as_str()).collect();
-
-    dp!(stripe_set);
+    let patterns = PatternSet::new(&data.stripes);
 
     let mut count = 0;
     for target in data.targets.iter() {
-        let test = TestTarget {
-            stripe_set: &stripe_set,
-            target,
-            max_len: max_stripe_len,
-        };
-
-        dp!(test);
-
-        let mut dmap = DijkstraMap::new(&test, DijkstraConfig { print_1000: false });
-        let costs = dmap.run((0, 0));
-        if costs.contains_key(&target.len()) {
-            let path_count =
-                DijkstraMap::<TestTarget, usize>::count_all_paths(&0, &target.len(), &costs);
-
-            count += path_count;
-        }
+        let path_count = patterns.count_decompositions(target);
+        debug_println!("{}: {}", target, path_count);
+        count += path_count;
     }
 
     println!("{count}");
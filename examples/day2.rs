@@ -1,10 +1,12 @@
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::Args;
+use aoc2024::{
+    reports::{analyse, StepBounds},
+    Args,
+};
 use clap::Parser;
-use itertools::FoldWhile::{Continue, Done};
-use itertools::Itertools;
+use debug_print::debug_println;
 use nom::{character::complete::*, combinator::*, multi::*, sequence::*, *};
 
 const TEST_INPUT: &str = "7 6 4 2 1
@@ -42,63 +44,26 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn check_line(line: &Vec<i32>) -> bool {
-    let result = line.iter().tuple_windows().fold_while(None, |acc, (a, b)| {
-        let diff = (b - a).abs();
-        let dir = (b - a).signum();
-        if diff > 3 || diff < 1 {
-            Done(Some((false, 0)))
-        } else {
-            match acc {
-                None => Continue(Some((true, dir))),
-                Some((_, edir)) if edir == dir => Continue(Some((true, dir))),
-                _ => Done(Some((false, 0))),
-            }
-        }
-    });
-    !result.is_done()
-}
-
-fn check_line2(line: &Vec<i32>) -> bool {
-    if check_line(line) {
-        return true;
-    }
-
-    // remove values one at a time to test
-    for n in 0..line.len() {
-        let line2 = {
-            let mut r = line.clone();
-            r.remove(n);
-            r
-        };
-
-        if check_line(&line2) {
-            return true;
-        }
-    }
-
-    false
+fn count_safe(data: &Data, max_removals: usize) -> usize {
+    data.data
+        .iter()
+        .filter(|line| {
+            let safety = analyse(line, StepBounds::default(), max_removals);
+            debug_println!("{:?}: {:?}", line, safety);
+            safety.is_some()
+        })
+        .count()
 }
 
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
-    let result = data
-        .data
-        .iter()
-        .map(|line| check_line(line))
-        .filter(|line| *line)
-        .count();
+    let result = count_safe(&data, 0);
     println!("{:?}", result);
 
     // part 2
 
-    let result = data
-        .data
-        .iter()
-        .map(|line| check_line2(line))
-        .filter(|line| *line)
-        .count();
+    let result = count_safe(&data, 1);
     println!("{:?}", result);
 
     Ok(())
@@ -1,8 +1,5 @@
-use anyhow::Error;
-use aoc2024::{
-    dijkstra::{DijkstraConfig, DijkstraInput, DijkstraMap},
-    dp, Args,
-};
+use anyhow::{anyhow, Error};
+use aoc2024::{dp, racetrack::RaceTrack, Args};
 use character::complete::{multispace0, one_of};
 use clap::Parser;
 use debug_print::debug_println;
@@ -105,64 +102,18 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl DijkstraInput for Data {
-    type Cost = i32;
-
-    type Index = (i32, i32);
-
-    fn get_adjacent(&self, pos: &Self::Index) -> Vec<(Self::Cost, Self::Index)> {
-        let mut v = vec![];
-
-        macro_rules! adj_test {
-            ($dr:expr, $dc:expr) => {{
-                let pos = (pos.0 + $dr, pos.1 + $dc);
-                if self.map.get(&pos).is_some_and(|t| *t == Tile::Empty) {
-                    v.push((1, pos));
-                }
-            }};
-        }
-
-        adj_test!(-1, 0);
-        adj_test!(1, 0);
-        adj_test!(0, -1);
-        adj_test!(0, 1);
-
-        v
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let mut dmap =
-        DijkstraMap::<Data, (i32, i32)>::new(&data, DijkstraConfig { print_1000: false });
-    let costs = dmap.run((0, data.start));
-
-    let initial_end_cost = costs.get(&data.end).unwrap().0;
-
-    dp!(initial_end_cost);
+    let is_track = |pos| data.map.get(&pos) == Some(&Tile::Empty);
+    let track = RaceTrack::new(data.width, data.height, is_track, data.start, data.end)
+        .ok_or_else(|| anyhow!("track is not a single path"))?;
 
-    // run cheats
-    let mut count = 0;
-    for cheat_loc in data.map.iter().filter_map(|(k, v)| match v {
-        Tile::Empty => None,
-        Tile::Wall => Some(k.clone()),
-    }) {
-        let mut map = data.clone();
-        *map.map.get_mut(&cheat_loc).unwrap() = Tile::Empty;
-        let mut dmap =
-            DijkstraMap::<Data, (i32, i32)>::new(&map, DijkstraConfig { print_1000: false });
-        let costs = dmap.run((0, data.start));
+    debug_println!("savings = {:?}", track.cheat_savings(2));
 
-        let end_cost = costs.get(&data.end).unwrap().0;
-
-        let cost_diff = initial_end_cost - end_cost;
-        if cost_diff >= 100 {
-            count += 1;
-        }
-    }
+    let count = track.count_cheats(2, 100);
     println!("{}", count);
 
     Ok(())
@@ -1,12 +1,8 @@
-use anyhow::Error;
-use aoc2024::{
-    dijkstra::{DijkstraConfig, DijkstraInput, DijkstraMap},
-    dp, Args,
-};
+use anyhow::{anyhow, Error};
+use aoc2024::{dp, racetrack::RaceTrack, Args};
 use character::complete::{multispace0, one_of};
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use multi::many1;
 use nom::*;
 use sequence::terminated;
@@ -106,77 +102,18 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-impl DijkstraInput for Data {
-    type Cost = i32;
-
-    type Index = (i32, i32);
-
-    fn get_adjacent(&self, pos: &Self::Index) -> Vec<(Self::Cost, Self::Index)> {
-        let mut v = vec![];
-
-        macro_rules! adj_test {
-            ($dr:expr, $dc:expr) => {{
-                let pos = (pos.0 + $dr, pos.1 + $dc);
-                if self.map.get(&pos).is_some_and(|t| *t == Tile::Empty) {
-                    v.push((1, pos));
-                }
-            }};
-        }
-
-        adj_test!(-1, 0);
-        adj_test!(1, 0);
-        adj_test!(0, -1);
-        adj_test!(0, 1);
-
-        v
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let mut dmap =
-        DijkstraMap::<Data, (i32, i32)>::new(&data, DijkstraConfig { print_1000: false });
-    let costs = dmap.run((0, data.start));
-
-    let initial_end_cost = costs.get(&data.end).unwrap().0;
-
-    dp!(initial_end_cost);
-
-    let cheat_combinations = data
-        .map
-        .iter()
-        .filter_map(|(k, v)| match v {
-            Tile::Empty => Some(k.clone()),
-            Tile::Wall => None,
-        })
-        .combinations(2)
-        .filter(|combs| {
-            let c1 = combs[0];
-            let c2 = combs[1];
-
-            let dist = (c1.0 - c2.0).abs() + (c1.1 - c2.1).abs();
-            dist >= 2 && dist <= 20
-        })
-        .map(|combs| (combs[0], combs[1]))
-        .collect_vec();
-
-    debug_println!("{}", cheat_combinations.len());
-
-    //// run cheats
-    let mut count = 0;
-    for (c1, c2) in cheat_combinations {
-        let added_cost = (c1.0 - c2.0).abs() + (c1.1 - c2.1).abs();
-        let cost1 = costs[&c2].0.min(costs[&c1].0);
-        let cost2 = costs[&c2].0.max(costs[&c1].0);
-        let new_cost = initial_end_cost - cost2 + cost1 + added_cost;
-        let cost_savings = initial_end_cost - new_cost;
-        if cost_savings >= 100 {
-            count += 1;
-        }
-    }
+    let is_track = |pos| data.map.get(&pos) == Some(&Tile::Empty);
+    let track = RaceTrack::new(data.width, data.height, is_track, data.start, data.end)
+        .ok_or_else(|| anyhow!("track is not a single path"))?;
+
+    debug_println!("savings = {:?}", track.cheat_savings(20));
+
+    let count = track.count_cheats(20, 100);
     println!("{}", count);
 
     Ok(())
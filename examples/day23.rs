@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{dp, graph::Graph, Args};
 use bytes::complete::tag;
 use character::complete::{alpha1, multispace0};
 use clap::Parser;
@@ -9,11 +9,7 @@ use itertools::Itertools;
 use multi::many1;
 use nom::*;
 use sequence::{separated_pair, terminated};
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    path::Path,
-};
+use std::{fs, path::Path};
 
 const TEST_INPUT: &str = "kh-tc
 qp-kh
@@ -86,72 +82,20 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn build_connections(data: &Vec<(String, String)>) -> HashMap<String, HashSet<String>> {
-    let mut hm = HashMap::new();
-    data.into_iter().for_each(|(c1, c2)| {
-        hm.entry(c1.clone())
-            .or_insert(HashSet::new())
-            .insert(c2.clone());
-        hm.entry(c2.clone())
-            .or_insert(HashSet::new())
-            .insert(c1.clone());
-    });
-    hm
-}
-
-fn path_exists_aux(
-    prev: Vec<&str>,
-    s: &str,
-    e: &str,
-    data: &HashMap<String, HashSet<String>>,
-) -> bool {
-    if prev.contains(&s) {
-        return false;
-    }
-    let set = data.get(s).unwrap();
-    if set.contains(e) {
-        return true;
-    }
-
-    for i in set.iter() {
-        let mut p = prev.clone();
-        p.push(i);
-        if path_exists_aux(p, i, e, data) {
-            return true;
-        }
-    }
-
-    false
-}
-
-fn path_exists(s: &str, e: &str, data: &HashMap<String, HashSet<String>>) -> bool {
-    path_exists_aux(vec![], s, e, data)
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
     dp!(data);
 
-    let connections = build_connections(&data.data);
-
-    dp!(connections);
-
-    let mut valid_combinations = vec![];
-    for comb in connections.keys().combinations(3) {
-        let (c1, c2, c3) = (comb[0], comb[1], comb[2]);
+    let graph = Graph::from_edges(data.data.iter().map(|(a, b)| (a.as_str(), b.as_str())));
 
-        if !c1.starts_with("t") && !c2.starts_with("t") && !c3.starts_with("t") {
-            continue;
-        }
+    let valid_combinations = graph
+        .triangles()
+        .into_iter()
+        .filter(|tri| tri.iter().any(|&c| graph.name(c).starts_with("t")))
+        .collect_vec();
 
-        if path_exists(c1, c2, &connections)
-            && path_exists(c1, c3, &connections)
-            && path_exists(c2, c3, &connections)
-        {
-            valid_combinations.push((c1, c2, c3));
-        }
-    }
+    dp!(valid_combinations);
 
     println!("{:?}", valid_combinations.len());
 
@@ -1,5 +1,5 @@
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{circuit, dp, Args};
 use bytes::complete::tag;
 use character::complete::{alphanumeric1, digit1, multispace0, multispace1};
 use clap::Parser;
@@ -8,7 +8,12 @@ use itertools::Itertools;
 use multi::{many1, separated_list1};
 use nom::*;
 use sequence::{preceded, separated_pair, terminated, tuple};
-use std::{cmp::Reverse, collections::HashMap, fs, path::Path};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 const TEST_INPUT: &str = "x00: 1
 x01: 0
@@ -71,6 +76,36 @@ enum Gate {
     Or(Signal, Signal, Signal),
 }
 
+#[derive(Parser, Debug)]
+struct ExportArgs {
+    #[command(flatten)]
+    args: Args,
+
+    /// Write the parsed circuit as a Graphviz digraph
+    #[arg(long)]
+    dot: Option<PathBuf>,
+
+    /// Write the parsed circuit as a structural Verilog module
+    #[arg(long)]
+    verilog: Option<PathBuf>,
+}
+
+impl From<&Gate> for circuit::Gate {
+    fn from(gate: &Gate) -> Self {
+        use circuit::GateKind;
+        let (kind, in1, in2, out) = match gate {
+            Gate::Xor(a, b, out) => (GateKind::Xor, a, b, out),
+            Gate::And(a, b, out) => (GateKind::And, a, b, out),
+            Gate::Or(a, b, out) => (GateKind::Or, a, b, out),
+        };
+        circuit::Gate {
+            kind,
+            inputs: (in1.name.clone(), in2.name.clone()),
+            output: out.name.clone(),
+        }
+    }
+}
+
 impl Gate {
     fn output_name(&self) -> &str {
         match self {
@@ -140,9 +175,7 @@ fn parse_data(i: &str) -> IResult<&str, Data> {
     Ok((i, data))
 }
 
-fn read_data() -> Result<Data, Error> {
-    let args = Args::parse();
-
+fn read_data(args: Args) -> Result<Data, Error> {
     let contents = args.file.map_or(Ok(TEST_INPUT.to_string()), |input| {
         let file = Path::new(&input);
         fs::read_to_string(file)
@@ -439,11 +472,25 @@ fn orig_test(gates: &Vec<Gate>, orig_signals: &HashMap<String, Option<i8>>) {
     println!("bits to flip:  {:>50b}", bits_to_flip);
 }
 
+fn export_circuit(args: &ExportArgs, gates: &[Gate]) -> Result<(), Error> {
+    let gates = gates.iter().map(circuit::Gate::from).collect_vec();
+    if let Some(path) = &args.dot {
+        fs::write(path, circuit::to_dot(&gates))?;
+    }
+    if let Some(path) = &args.verilog {
+        fs::write(path, circuit::to_verilog("day24", &gates))?;
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Error> {
-    let data = read_data()?;
+    let args = ExportArgs::parse();
+    let data = read_data(args.args.clone())?;
 
     dp!(data);
 
+    export_circuit(&args, &data.gates)?;
+
     let orig_signals = {
         let mut signals = HashMap::new();
         data.inputs.into_iter().for_each(|signal| {
@@ -1,63 +1,26 @@
 use std::{fs, path::Path};
 
-use anyhow::Error;
-use aoc2024::Args;
+use anyhow::{anyhow, Error};
+use aoc2024::{
+    dp,
+    instructions::{evaluate, Scanner, Token},
+    Args,
+};
 use clap::Parser;
 use debug_print::debug_println;
-use nom::{bytes::complete::*, character::complete::*, combinator::*, sequence::*, *};
 
 const TEST_INPUT: &str =
     "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
 #[derive(Debug)]
 struct Data {
-    data: Vec<(i32, i32)>,
+    data: Vec<Token>,
 }
 
-fn parse_data(i: &str) -> IResult<&str, Data> {
-    let parse_mul = || {
-        terminated(
-            preceded(
-                tag("mul("),
-                separated_pair(
-                    map_res(digit1::<&str, ()>, str::parse),
-                    tag(","),
-                    map_res(digit1, str::parse),
-                ),
-            ),
-            tag(")"),
-        )
-    };
-
-    let mut data = vec![];
-    let mut rest = i;
-    let mut enabled = true;
-    while rest.len() > 0 {
-        if enabled {
-            if let Ok((remaining, _)) = tag::<_, _, ()>("don't()")(rest) {
-                debug_println!("disabled");
-                enabled = false;
-                rest = remaining;
-            } else if let Ok((remaining, problem)) = parse_mul()(rest) {
-                debug_println!("{:?}", problem);
-                data.push(problem);
-                rest = remaining;
-            } else {
-                rest = &rest[1..rest.len()];
-            }
-        } else {
-            if let Ok((remaining, _)) = tag::<_, _, ()>("do()")(rest) {
-                debug_println!("enabled");
-                enabled = true;
-                rest = remaining;
-            } else {
-                rest = &rest[1..rest.len()];
-            }
-        }
+fn parse_data(i: &str) -> Data {
+    Data {
+        data: Scanner::default().tokens(i),
     }
-
-    let data = Data { data };
-    Ok(("", data))
 }
 
 fn read_data() -> Result<Data, Error> {
@@ -68,16 +31,15 @@ fn read_data() -> Result<Data, Error> {
         fs::read_to_string(file)
     })?;
 
-    let data = parse_data(&contents);
-    let data = data.map_err(|err| err.map_input(|s| s.to_string()))?;
-    assert!(data.0 == "");
-    Ok(data.1)
+    Ok(parse_data(&contents))
 }
 
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
-    let result = data.data.iter().fold(0, |acc, (a, b)| acc + a * b);
+    dp!(data);
+
+    let result = evaluate(&data.data, true).ok_or_else(|| anyhow!("sum of products overflowed"))?;
 
     println!("{:?}", result);
 
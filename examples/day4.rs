@@ -1,11 +1,9 @@
-use std::collections::HashMap;
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::Args;
+use aoc2024::{wordsearch::LetterGrid, Args};
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use nom::{character::complete::*, multi::*, sequence::*, *};
 
 const TEST_INPUT: &str = "MMMSXXMASM
@@ -21,9 +19,7 @@ MXMXAXMASX";
 
 #[derive(Debug)]
 struct Data {
-    data: HashMap<(usize, usize), char>,
-    width: usize,
-    height: usize,
+    data: Vec<Vec<char>>,
 }
 
 fn parse_data(i: &str) -> IResult<&str, Data> {
@@ -32,20 +28,8 @@ fn parse_data(i: &str) -> IResult<&str, Data> {
 
     debug_println!("{:?}", problems);
 
-    let mut data = HashMap::new();
-    let mut width = 0;
-    let height = problems.len();
-    for (row, v) in problems.iter().enumerate() {
-        width = v.len();
-        for (col, c) in v.chars().enumerate() {
-            data.insert((row, col), c);
-        }
-    }
-
     let data = Data {
-        data,
-        width,
-        height,
+        data: problems.iter().map(|v| v.chars().collect()).collect(),
     };
     Ok((i, data))
 }
@@ -64,54 +48,18 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn check_position(r: i32, c: i32, data: &Data) -> usize {
-    macro_rules! get {
-        ($r:expr, $c:expr) => {
-            if $r < 0 || $c < 0 {
-                None
-            } else {
-                data.data.get(&($r as usize, $c as usize))
-            }
-        };
-    }
-
-    let mut count = 0;
-
-    macro_rules! check {
-        (($r1:expr, $c1:expr), ($r2:expr, $c2:expr), ($r3:expr, $c3:expr), ($r4:expr, $c4:expr)) => {
-            if get![$r1, $c1] == Some(&'X')
-                && get![$r2, $c2] == Some(&'M')
-                && get![$r3, $c3] == Some(&'A')
-                && get![$r4, $c4] == Some(&'S')
-            {
-                count += 1;
-            }
-        };
-    }
-
-    check!((r, c), (r, c + 1), (r, c + 2), (r, c + 3));
-    check!((r, c), (r, c - 1), (r, c - 2), (r, c - 3));
-    check!((r, c), (r + 1, c), (r + 2, c), (r + 3, c));
-    check!((r, c), (r - 1, c), (r - 2, c), (r - 3, c));
-
-    check!((r, c), (r + 1, c + 1), (r + 2, c + 2), (r + 3, c + 3));
-    check!((r, c), (r - 1, c + 1), (r - 2, c + 2), (r - 3, c + 3));
-    check!((r, c), (r + 1, c - 1), (r + 2, c - 2), (r + 3, c - 3));
-    check!((r, c), (r - 1, c - 1), (r - 2, c - 2), (r - 3, c - 3));
-
-    count
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
-    let mut count = 0;
-    for row in 0..data.height {
-        for col in 0..data.width {
-            let r = check_position(row as i32, col as i32, &data);
-            count += r;
-        }
-    }
+    let grid = LetterGrid::new(&data.data);
+    let matches = grid.find_word("XMAS", false);
+
+    debug_println!(
+        "{}",
+        grid.render_highlighted(matches.iter().flat_map(|m| m.cells.iter().copied()))
+    );
+
+    let count = matches.len();
 
     println!("{:?}", count);
 
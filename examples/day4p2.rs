@@ -1,11 +1,12 @@
-use std::collections::HashMap;
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::Args;
+use aoc2024::{
+    wordsearch::{LetterGrid, Template},
+    Args,
+};
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use nom::{character::complete::*, multi::*, sequence::*, *};
 
 const TEST_INPUT: &str = "MMMSXXMASM
@@ -21,9 +22,7 @@ MXMXAXMASX";
 
 #[derive(Debug)]
 struct Data {
-    data: HashMap<(usize, usize), char>,
-    width: usize,
-    height: usize,
+    data: Vec<Vec<char>>,
 }
 
 fn parse_data(i: &str) -> IResult<&str, Data> {
@@ -32,20 +31,8 @@ fn parse_data(i: &str) -> IResult<&str, Data> {
 
     debug_println!("{:?}", problems);
 
-    let mut data = HashMap::new();
-    let mut width = 0;
-    let height = problems.len();
-    for (row, v) in problems.iter().enumerate() {
-        width = v.len();
-        for (col, c) in v.chars().enumerate() {
-            data.insert((row, col), c);
-        }
-    }
-
     let data = Data {
-        data,
-        width,
-        height,
+        data: problems.iter().map(|v| v.chars().collect()).collect(),
     };
     Ok((i, data))
 }
@@ -64,77 +51,19 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn check_position(r: i32, c: i32, data: &Data) -> usize {
-    macro_rules! get {
-        ($r:expr, $c:expr) => {
-            if $r < 0 || $c < 0 {
-                None
-            } else {
-                data.data.get(&($r as usize, $c as usize))
-            }
-        };
-    }
-
-    let mut count = 0;
-
-    macro_rules! check {
-        (($r1:expr, $c1:expr), ($r2:expr, $c2:expr), ($r3:expr, $c3:expr), ($r4:expr, $c4:expr), ($r5:expr, $c5:expr)) => {
-            if get![$r1, $c1] == Some(&'M')
-                && get![$r2, $c2] == Some(&'M')
-                && get![$r3, $c3] == Some(&'A')
-                && get![$r4, $c4] == Some(&'S')
-                && get![$r5, $c5] == Some(&'S')
-            {
-                count += 1;
-            }
-        };
-    }
-
-    check!(
-        (r - 1, c - 1),
-        (r + 1, c - 1),
-        (r, c),
-        (r - 1, c + 1),
-        (r + 1, c + 1)
-    );
-
-    check!(
-        (r - 1, c + 1),
-        (r + 1, c + 1),
-        (r, c),
-        (r - 1, c - 1),
-        (r + 1, c - 1)
-    );
+fn main() -> Result<(), Error> {
+    let data = read_data()?;
 
-    check!(
-        (r - 1, c - 1),
-        (r - 1, c + 1),
-        (r, c),
-        (r + 1, c - 1),
-        (r + 1, c + 1)
-    );
+    let grid = LetterGrid::new(&data.data);
+    let template = Template::parse(&["M.S", ".A.", "M.S"], '.');
+    let matches = grid.find_template(&template, true);
 
-    check!(
-        (r + 1, c - 1),
-        (r + 1, c + 1),
-        (r, c),
-        (r - 1, c - 1),
-        (r - 1, c + 1)
+    debug_println!(
+        "{}",
+        grid.render_highlighted(matches.iter().flat_map(|m| m.cells.iter().copied()))
     );
 
-    count
-}
-
-fn main() -> Result<(), Error> {
-    let data = read_data()?;
-
-    let mut count = 0;
-    for row in 0..data.height {
-        for col in 0..data.width {
-            let r = check_position(row as i32, col as i32, &data);
-            count += r;
-        }
-    }
+    let count = matches.len();
 
     println!("{:?}", count);
 
@@ -1,13 +1,10 @@
-use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
 use std::{fs, path::Path};
 
-use anyhow::Error;
-use aoc2024::{dp, Args};
+use anyhow::{anyhow, Error};
+use aoc2024::{dp, precedence::Precedence, Args};
 use character::streaming::multispace0;
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use nom::{
     branch::*, bytes::complete::*, character::complete::*, combinator::*, multi::*, sequence::*, *,
 };
@@ -87,44 +84,15 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn build_sort_fun(order: HashMap<i32, HashSet<i32>>) -> impl Fn(&i32, &i32) -> Ordering {
-    move |a, b| {
-        if a == b {
-            return Ordering::Equal;
-        }
-
-        if let Some(v) = order.get(a) {
-            if v.contains(b) {
-                return Ordering::Less;
-            }
-        }
-
-        Ordering::Greater
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
     dp!(data);
 
-    let order_table = {
-        let mut m = HashMap::new();
-        for (k, v) in data.orderings.iter() {
-            m.entry(*k).or_insert_with(HashSet::new).insert(*v);
-        }
-        m
-    };
-
-    let sort_func = build_sort_fun(order_table);
+    let rules = Precedence::new(data.orderings.iter().copied());
 
     let mut result = 0;
     for update in data.updates.iter() {
-        let update_sorted = {
-            let mut sorted = update.clone();
-            sorted.sort_by(&sort_func);
-            sorted
-        };
-        if &update_sorted == update {
+        if rules.is_ordered(update) {
             if update.len() % 2 == 1 {
                 result += update[update.len() / 2];
             } else {
@@ -139,12 +107,12 @@ fn main() -> Result<(), Error> {
 
     let mut result = 0;
     for update in data.updates.iter() {
-        let update_sorted = {
-            let mut sorted = update.clone();
-            sorted.sort_by(&sort_func);
-            sorted
-        };
-        if &update_sorted != update {
+        let violation = rules.first_violation(update);
+        dp!(violation);
+        if violation.is_some() {
+            let update_sorted = rules
+                .topological_sort(update)
+                .map_err(|cycle| anyhow!("rules form a cycle: {:?}", cycle))?;
             if update.len() % 2 == 1 {
                 result += update_sorted[update.len() / 2];
             } else {
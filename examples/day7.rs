@@ -1,11 +1,13 @@
-use std::collections::VecDeque;
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{
+    dp,
+    operators::{Add, Mul, OperatorSolver},
+    Args,
+};
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use nom::{
     branch::*, bytes::complete::*, character::complete::*, combinator::*, multi::*, sequence::*, *,
 };
@@ -49,35 +51,18 @@ fn read_data() -> Result<Data, Error> {
     Ok(data.1)
 }
 
-fn test_line((target, nums): &(i64, Vec<i64>)) -> bool {
-    let mut queue = VecDeque::new();
-    queue.push_back((nums[0], nums.iter().skip(1).cloned().collect_vec()));
-
-    while let Some((this_target, others)) = queue.pop_front() {
-        if others.is_empty() {
-            if this_target == *target {
-                return true;
-            } else {
-                continue;
-            }
-        } else {
-            let next_num = others[0];
-            let others = others.iter().skip(1).cloned().collect_vec();
-            queue.push_back((this_target * next_num, others.clone()));
-            queue.push_back((this_target + next_num, others));
-        }
-    }
-    false
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
     dp!(data);
 
+    let solver = OperatorSolver::new(vec![Box::new(Add), Box::new(Mul)]);
+    let solutions = solver.solve_all(&data.data);
+
     let mut count = 0;
-    for line in data.data {
-        let result = test_line(&line);
-        if result {
+    for (line, solution) in data.data.iter().zip(solutions) {
+        if let Some(ops) = solution {
+            debug_assert_eq!(OperatorSolver::evaluate(&line.1, &ops), Some(line.0));
+            debug_println!("{} = {}", line.0, OperatorSolver::format(&line.1, &ops));
             count += line.0;
         }
     }
@@ -1,11 +1,13 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::{fs, path::Path};
 
 use anyhow::Error;
-use aoc2024::{dp, Args};
+use aoc2024::{
+    antennas::{antinodes, AntinodeRule},
+    dp, Args,
+};
 use clap::Parser;
 use debug_print::debug_println;
-use itertools::Itertools;
 use nom::{
     branch::*, bytes::complete::*, character::complete::*, combinator::*, multi::*, sequence::*, *,
 };
@@ -83,25 +85,6 @@ fn identify_antennas(map: &HashMap<(i32, i32), char>) -> HashMap<char, Vec<(i32,
     })
 }
 
-fn mirrors_of((r1, c1): &(i32, i32), (r2, c2): &(i32, i32)) -> ((i32, i32), (i32, i32)) {
-    let dr = r2 - r1;
-    let dc = c2 - c1;
-    ((r2 + dr, c2 + dc), (r1 - dr, c1 - dc))
-}
-
-fn display_antinode_map(map: &HashSet<(i32, i32)>, width: usize, height: usize) {
-    for row in 0..height {
-        for col in 0..width {
-            if map.contains(&(row as i32, col as i32)) {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!("");
-    }
-}
-
 fn main() -> Result<(), Error> {
     let data = read_data()?;
 
@@ -111,25 +94,19 @@ fn main() -> Result<(), Error> {
 
     dp!(antennas);
 
-    let mut antinode_map = HashSet::new();
-
-    for (&_antenna, positions) in antennas.iter() {
-        positions.iter().combinations(2).for_each(|pos| {
-            let p1 = pos[0];
-            let p2 = pos[1];
-            let (anti1, anti2) = mirrors_of(p1, p2);
-            if data.data.contains_key(&anti1) {
-                antinode_map.insert(anti1);
-            }
-            if data.data.contains_key(&anti2) {
-                antinode_map.insert(anti2);
-            }
-        });
-    }
+    let antinode_map = antinodes(
+        &antennas,
+        AntinodeRule::DistanceRatio,
+        data.width,
+        data.height,
+    );
 
-    let result = antinode_map.len();
+    debug_println!(
+        "{}",
+        aoc2024::antennas::render(&antennas, &antinode_map, data.width, data.height)
+    );
 
-    //display_antinode_map(&antinode_map, data.width, data.height);
+    let result = antinode_map.len();
 
     println!("{result}");
 
@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use num::Integer;
+
+/// Which points on the line through two antennas are antinodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AntinodeRule {
+    /// Points where one antenna is twice as far away as the other.
+    DistanceRatio,
+    /// Every lattice point on the line.
+    Collinear,
+}
+
+fn in_bounds((r, c): (i32, i32), width: usize, height: usize) -> bool {
+    r >= 0 && c >= 0 && (r as usize) < height && (c as usize) < width
+}
+
+/// Every integer point on the line through `a` and `b` that lies on a
+/// `width` by `height` grid. The step is the offset divided by its GCD,
+/// so points between lattice-aligned antennas aren't skipped.
+pub fn line_points(a: (i32, i32), b: (i32, i32), width: usize, height: usize) -> Vec<(i32, i32)> {
+    let (dr, dc) = (b.0 - a.0, b.1 - a.1);
+    let g = dr.gcd(&dc);
+    if g == 0 {
+        return vec![];
+    }
+    let (dr, dc) = (dr / g, dc / g);
+
+    let mut points = vec![];
+    for dir in [1, -1] {
+        let start = if dir == 1 { a } else { (a.0 - dr, a.1 - dc) };
+        let mut p = start;
+        while in_bounds(p, width, height) {
+            points.push(p);
+            p = (p.0 + dr * dir, p.1 + dc * dir);
+        }
+    }
+    points
+}
+
+/// Points on the line through `a` and `b` where the distance to one is
+/// twice the distance to the other: the two outside `a` and `b`, and
+/// the two trisection points between them when they fall on the grid.
+pub fn ratio_points(a: (i32, i32), b: (i32, i32), width: usize, height: usize) -> Vec<(i32, i32)> {
+    let (dr, dc) = (b.0 - a.0, b.1 - a.1);
+    if (dr, dc) == (0, 0) {
+        return vec![];
+    }
+
+    let mut points = vec![(b.0 + dr, b.1 + dc), (a.0 - dr, a.1 - dc)];
+    if dr % 3 == 0 && dc % 3 == 0 {
+        let (tr, tc) = (dr / 3, dc / 3);
+        points.push((a.0 + tr, a.1 + tc));
+        points.push((b.0 - tr, b.1 - tc));
+    }
+    points.retain(|&p| in_bounds(p, width, height));
+    points
+}
+
+/// Antinodes for a single pair of antennas under `rule`.
+pub fn pair_antinodes(
+    a: (i32, i32),
+    b: (i32, i32),
+    rule: AntinodeRule,
+    width: usize,
+    height: usize,
+) -> Vec<(i32, i32)> {
+    match rule {
+        AntinodeRule::DistanceRatio => ratio_points(a, b, width, height),
+        AntinodeRule::Collinear => line_points(a, b, width, height),
+    }
+}
+
+/// Antinodes from every pair of antennas sharing a frequency.
+pub fn antinodes(
+    groups: &HashMap<char, Vec<(i32, i32)>>,
+    rule: AntinodeRule,
+    width: usize,
+    height: usize,
+) -> HashSet<(i32, i32)> {
+    groups
+        .values()
+        .flat_map(|positions| positions.iter().tuple_combinations())
+        .flat_map(|(&a, &b)| pair_antinodes(a, b, rule, width, height))
+        .collect()
+}
+
+/// The antennas on the left and the antinodes on the right, with
+/// antennas that are also antinodes keeping their frequency.
+pub fn render(
+    groups: &HashMap<char, Vec<(i32, i32)>>,
+    antinodes: &HashSet<(i32, i32)>,
+    width: usize,
+    height: usize,
+) -> String {
+    let antennas: HashMap<(i32, i32), char> = groups
+        .iter()
+        .flat_map(|(&freq, positions)| positions.iter().map(move |&p| (p, freq)))
+        .collect();
+
+    let mut s = String::new();
+    for row in 0..height as i32 {
+        let original = (0..width as i32)
+            .map(|col| antennas.get(&(row, col)).copied().unwrap_or('.'))
+            .collect::<String>();
+        let marked = (0..width as i32)
+            .map(|col| match antennas.get(&(row, col)) {
+                Some(&freq) => freq,
+                None if antinodes.contains(&(row, col)) => '#',
+                None => '.',
+            })
+            .collect::<String>();
+        s.push_str(&format!("{original}  {marked}\n"));
+    }
+    s
+}
@@ -0,0 +1,163 @@
+/// An 8-bit grayscale image, 0 is black and 255 is white.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    pub fn new(width: usize, height: usize, fill: u8) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// Plot points as black pixels on a white background. Points
+    /// outside the image are ignored.
+    pub fn from_points(width: usize, height: usize, points: &[(i64, i64)]) -> Self {
+        let mut bitmap = Self::new(width, height, 255);
+        for &(x, y) in points {
+            if x >= 0 && y >= 0 {
+                bitmap.set(x as usize, y as usize, 0);
+            }
+        }
+        bitmap
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: u8) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = value;
+        }
+    }
+
+    /// Tile `frames` left to right, top to bottom, `columns` per row
+    /// with `padding` gray pixels around each frame.
+    pub fn contact_sheet(frames: &[Bitmap], columns: usize, padding: usize) -> Self {
+        let columns = columns.max(1);
+        let rows = frames.len().div_ceil(columns);
+        let cell_w = frames.iter().map(|f| f.width).max().unwrap_or(0) + padding;
+        let cell_h = frames.iter().map(|f| f.height).max().unwrap_or(0) + padding;
+
+        let mut sheet = Self::new(columns * cell_w + padding, rows * cell_h + padding, 128);
+        for (n, frame) in frames.iter().enumerate() {
+            let x0 = (n % columns) * cell_w + padding;
+            let y0 = (n / columns) * cell_h + padding;
+            for y in 0..frame.height {
+                let src = &frame.pixels[y * frame.width..(y + 1) * frame.width];
+                let dst = (y0 + y) * sheet.width + x0;
+                sheet.pixels[dst..dst + frame.width].copy_from_slice(src);
+            }
+        }
+        sheet
+    }
+
+    /// Binary PBM (P4). Pixels darker than mid gray are set.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut out = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for byte in row.chunks(8) {
+                let bits = byte
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &v)| v < 128)
+                    .fold(0u8, |acc, (n, _)| acc | (0x80 >> n));
+                out.push(bits);
+            }
+        }
+        out
+    }
+
+    /// Binary PGM (P5).
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut out = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+
+    /// 8-bit grayscale PNG. The image data is written as uncompressed
+    /// deflate blocks so no compression library is needed.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        let mut ihdr = vec![];
+        ihdr.extend_from_slice(&(self.width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // bit depth 8, grayscale, deflate, no filter, no interlace
+        ihdr.extend_from_slice(&[8, 0, 0, 0, 0]);
+        png_chunk(&mut out, b"IHDR", &ihdr);
+
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            // filter type none
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+
+        png_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate, 32k window, no preset dictionary, fastest
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        out.push(last as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
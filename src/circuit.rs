@@ -0,0 +1,266 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use itertools::Itertools;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+}
+
+impl GateKind {
+    fn name(&self) -> &'static str {
+        match self {
+            GateKind::And => "AND",
+            GateKind::Or => "OR",
+            GateKind::Xor => "XOR",
+        }
+    }
+
+    fn color(&self) -> &'static str {
+        match self {
+            GateKind::And => "lightblue",
+            GateKind::Or => "palegreen",
+            GateKind::Xor => "salmon",
+        }
+    }
+
+    fn verilog_primitive(&self) -> &'static str {
+        match self {
+            GateKind::And => "and",
+            GateKind::Or => "or",
+            GateKind::Xor => "xor",
+        }
+    }
+}
+
+/// A two input gate driving a single named output wire.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub inputs: (String, String),
+    pub output: String,
+}
+
+/// Wire level view of a list of gates.
+struct Netlist<'a> {
+    gates: &'a [Gate],
+    drivers: HashMap<&'a str, usize>,
+    /// Wires not driven by any gate, sorted by name.
+    inputs: Vec<&'a str>,
+    /// Driven wires not read by any gate, sorted by name.
+    outputs: Vec<&'a str>,
+    /// Driven wires read by at least one gate, sorted by name.
+    internal: Vec<&'a str>,
+}
+
+impl<'a> Netlist<'a> {
+    fn new(gates: &'a [Gate]) -> Self {
+        let drivers: HashMap<&str, usize> = gates
+            .iter()
+            .enumerate()
+            .map(|(idx, g)| (g.output.as_str(), idx))
+            .collect();
+        let read: HashSet<&str> = gates
+            .iter()
+            .flat_map(|g| [g.inputs.0.as_str(), g.inputs.1.as_str()])
+            .collect();
+
+        let inputs = read
+            .iter()
+            .filter(|w| !drivers.contains_key(*w))
+            .copied()
+            .sorted()
+            .collect();
+        let (internal, outputs) = drivers
+            .keys()
+            .copied()
+            .sorted()
+            .partition(|w| read.contains(w));
+
+        Self {
+            gates,
+            drivers,
+            inputs,
+            outputs,
+            internal,
+        }
+    }
+}
+
+/// Render the gates as a Graphviz digraph. Gates become nodes
+/// coloured by type, wires become labelled edges, and the primary
+/// inputs and outputs are each ranked together in name order.
+pub fn to_dot(gates: &[Gate]) -> String {
+    let net = Netlist::new(gates);
+    let mut out = String::new();
+
+    macro_rules! w {
+        ($($arg:tt)*) => {
+            writeln!(out, $($arg)*).unwrap()
+        };
+    }
+
+    w!("digraph circuit {{");
+    w!("    rankdir=LR;");
+    w!("    node [fontname=\"monospace\"];");
+
+    for wire in net.inputs.iter().chain(net.outputs.iter()) {
+        w!("    \"{}\" [shape=plaintext];", wire);
+    }
+    for (idx, gate) in net.gates.iter().enumerate() {
+        w!(
+            "    g{} [label=\"{}\\n{}\", shape=box, style=filled, fillcolor={}];",
+            idx,
+            gate.kind.name(),
+            gate.output,
+            gate.kind.color()
+        );
+    }
+
+    let source = |wire: &str| match net.drivers.get(wire) {
+        Some(idx) => format!("g{}", idx),
+        None => format!("\"{}\"", wire),
+    };
+
+    for (idx, gate) in net.gates.iter().enumerate() {
+        for wire in [&gate.inputs.0, &gate.inputs.1] {
+            w!("    {} -> g{} [label=\"{}\"];", source(wire), idx, wire);
+        }
+    }
+    for wire in net.outputs.iter() {
+        w!("    {} -> \"{}\";", source(wire), wire);
+    }
+
+    for bus in [&net.inputs, &net.outputs] {
+        if bus.is_empty() {
+            continue;
+        }
+        w!(
+            "    {{ rank=same; {} }}",
+            bus.iter().map(|w| format!("\"{}\";", w)).join(" ")
+        );
+        // invisible edges keep the bus in bit order within its rank
+        if bus.len() > 1 {
+            w!(
+                "    {} [style=invis];",
+                bus.iter().map(|w| format!("\"{}\"", w)).join(" -> ")
+            );
+        }
+    }
+
+    w!("}}");
+    out
+}
+
+/// Verilog-2005 reserved words.
+const VERILOG_KEYWORDS: &str =
+    "always and assign automatic begin buf bufif0 bufif1 case casex casez cell cmos config \
+     deassign default defparam design disable edge else end endcase endconfig endfunction \
+     endgenerate endmodule endprimitive endspecify endtable endtask event for force forever \
+     fork function generate genvar highz0 highz1 if ifnone incdir include initial inout input \
+     instance integer join large liblist library localparam macromodule medium module nand \
+     negedge nmos nor noshowcancelled not notif0 notif1 or output parameter pmos posedge \
+     primitive pull0 pull1 pulldown pullup pulsestyle_ondetect pulsestyle_onevent rcmos real \
+     realtime reg release repeat rnmos rpmos rtran rtranif0 rtranif1 scalared showcancelled \
+     signed small specify specparam strong0 strong1 supply0 supply1 table task time tran \
+     tranif0 tranif1 tri tri0 tri1 triand trior trireg unsigned use uwire vectored wait wand \
+     weak0 weak1 while wire wor xnor xor";
+
+/// `name` as a Verilog identifier. Keywords and anything that isn't a
+/// plain identifier are written escaped, `\and `, with the trailing
+/// space that ends an escaped identifier.
+fn verilog_ident(name: &str) -> String {
+    let simple = name
+        .chars()
+        .next()
+        .is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '$');
+    if simple && !VERILOG_KEYWORDS.split_whitespace().any(|word| word == name) {
+        name.to_string()
+    } else {
+        format!("\\{} ", name)
+    }
+}
+
+/// Render the gates as a structural Verilog module using the builtin
+/// `and`/`or`/`xor` primitives. Undriven wires become input ports and
+/// wires nobody reads become output ports. Wire names that are Verilog
+/// keywords are escaped.
+pub fn to_verilog(module_name: &str, gates: &[Gate]) -> String {
+    let net = Netlist::new(gates);
+    let mut out = String::new();
+
+    macro_rules! w {
+        ($($arg:tt)*) => {
+            writeln!(out, $($arg)*).unwrap()
+        };
+    }
+
+    let ports = net
+        .inputs
+        .iter()
+        .map(|w| format!("    input wire {}", verilog_ident(w)))
+        .chain(
+            net.outputs
+                .iter()
+                .map(|w| format!("    output wire {}", verilog_ident(w))),
+        )
+        .join(",\n");
+
+    w!("module {} (", verilog_ident(module_name));
+    w!("{}", ports);
+    w!(");");
+
+    if !net.internal.is_empty() {
+        w!();
+        for wire in net.internal.iter() {
+            w!("    wire {};", verilog_ident(wire));
+        }
+    }
+
+    w!();
+    for (idx, gate) in net.gates.iter().enumerate() {
+        w!(
+            "    {} g{} ({}, {}, {});",
+            gate.kind.verilog_primitive(),
+            idx,
+            verilog_ident(&gate.output),
+            verilog_ident(&gate.inputs.0),
+            verilog_ident(&gate.inputs.1)
+        );
+    }
+
+    w!();
+    w!("endmodule");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_wires_escaped() {
+        let gate = |kind, a: &str, b: &str, out: &str| Gate {
+            kind,
+            inputs: (a.to_string(), b.to_string()),
+            output: out.to_string(),
+        };
+        let gates = [
+            gate(GateKind::And, "x00", "y00", "and"),
+            gate(GateKind::Xor, "and", "end", "z00"),
+        ];
+        let verilog = to_verilog("adder", &gates);
+        assert!(verilog.contains("    input wire \\end ,\n"));
+        assert!(verilog.contains("    wire \\and ;\n"));
+        assert!(verilog.contains("and g0 (\\and , x00, y00);"));
+        assert!(verilog.contains("xor g1 (z00, \\and , \\end );"));
+    }
+}
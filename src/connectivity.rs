@@ -0,0 +1,144 @@
+use std::fmt::Display;
+
+/// Disjoint sets over `0..n` with path halving and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merge the sets holding `a` and `b`. Returns false if they were
+    /// already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (big, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+/// A position given to [`first_blocking_drop`] that is off the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    Start((i32, i32)),
+    End((i32, i32)),
+    /// The drop at `index` in the list.
+    Drop {
+        index: usize,
+        pos: (i32, i32),
+    },
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionError::Start((x, y)) => write!(f, "start {x},{y} is off the grid"),
+            PositionError::End((x, y)) => write!(f, "end {x},{y} is off the grid"),
+            PositionError::Drop { index, pos: (x, y) } => {
+                write!(f, "drop {index} at {x},{y} is off the grid")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PositionError {}
+
+/// The index into `drops` of the obstacle that first cuts `start` off
+/// from `end` on a `width` by `height` grid, with positions given as
+/// `(x, y)`. The first `initial` drops are taken to have already
+/// fallen, so the answer is never before them. Returns `None` if the
+/// two stay connected after every drop, or are already apart once the
+/// first `initial` have fallen, and an error if any position is off the
+/// grid.
+///
+/// Works backwards in time: with every obstacle in place the free
+/// cells are joined up, then obstacles are lifted from the last one
+/// until `start` and `end` meet. The drop lifted at that point is the
+/// one that cut them apart.
+pub fn first_blocking_drop(
+    width: usize,
+    height: usize,
+    drops: &[(i32, i32)],
+    initial: usize,
+    start: (i32, i32),
+    end: (i32, i32),
+) -> Result<Option<usize>, PositionError> {
+    let index = |(x, y): (i32, i32)| {
+        (x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height)
+            .then(|| y as usize * width + x as usize)
+    };
+    let start = index(start).ok_or(PositionError::Start(start))?;
+    let end = index(end).ok_or(PositionError::End(end))?;
+
+    // the cell of each drop, and the time each cell is first blocked,
+    // repeats don't matter
+    let mut cells = Vec::with_capacity(drops.len());
+    let mut blocked_at = vec![None; width * height];
+    for (t, &pos) in drops.iter().enumerate() {
+        let cell = index(pos).ok_or(PositionError::Drop { index: t, pos })?;
+        blocked_at[cell].get_or_insert(t);
+        cells.push(cell);
+    }
+
+    let mut free = blocked_at.iter().map(|t| t.is_none()).collect::<Vec<_>>();
+    let mut sets = UnionFind::new(width * height);
+    let join = |cell: usize, free: &[bool], sets: &mut UnionFind| {
+        let (x, y) = ((cell % width) as i32, (cell / width) as i32);
+        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+            if let Some(next) = index((x + dx, y + dy)).filter(|&next| free[next]) {
+                sets.union(cell, next);
+            }
+        }
+    };
+
+    for cell in 0..width * height {
+        if free[cell] {
+            join(cell, &free, &mut sets);
+        }
+    }
+    if free[start] && free[end] && sets.connected(start, end) {
+        return Ok(None);
+    }
+
+    for t in (initial..drops.len()).rev() {
+        let cell = cells[t];
+        if blocked_at[cell] != Some(t) {
+            continue;
+        }
+        free[cell] = true;
+        join(cell, &free, &mut sets);
+        if free[start] && free[end] && sets.connected(start, end) {
+            return Ok(Some(t));
+        }
+    }
+
+    Ok(None)
+}
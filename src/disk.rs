@@ -0,0 +1,176 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+/// A contiguous run of blocks belonging to one file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Extent {
+    pub file_id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Block layout of a disk. Extents are kept sorted by start position
+/// and every block not covered by an extent is free.
+#[derive(Debug, Clone)]
+pub struct DiskMap {
+    extents: Vec<Extent>,
+    size: usize,
+}
+
+impl DiskMap {
+    /// Build from the dense format, alternating file and free lengths
+    /// with files numbered in order of appearance.
+    pub fn from_dense(lengths: &[u32]) -> Self {
+        let mut extents = vec![];
+        let mut pos = 0;
+        for (n, chunk) in lengths.chunks(2).enumerate() {
+            let len = chunk[0] as usize;
+            if len > 0 {
+                extents.push(Extent {
+                    file_id: n,
+                    start: pos,
+                    len,
+                });
+            }
+            pos += len + chunk.get(1).map_or(0, |&v| v as usize);
+        }
+        Self { extents, size: pos }
+    }
+
+    pub fn extents(&self) -> &[Extent] {
+        &self.extents
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Free spans between the extents as `(start, len)` pairs.
+    pub fn free_spans(&self) -> Vec<(usize, usize)> {
+        let mut spans = vec![];
+        let mut pos = 0;
+        for ext in self.extents.iter() {
+            if ext.start > pos {
+                spans.push((pos, ext.start - pos));
+            }
+            pos = ext.start + ext.len;
+        }
+        if self.size > pos {
+            spans.push((pos, self.size - pos));
+        }
+        spans
+    }
+
+    /// Move single blocks from the end of the disk into the leftmost
+    /// free block until there are no gaps left between files.
+    pub fn compact_blocks(&mut self) {
+        let mut extents = std::mem::take(&mut self.extents);
+        let mut compacted = Vec::with_capacity(extents.len());
+        let mut pos = 0;
+        let mut next = 0;
+
+        while next < extents.len() {
+            let ext = extents[next];
+            if ext.start > pos {
+                // fill the gap from the tail, splitting the last extent
+                // if it doesn't fit
+                let gap = ext.start - pos;
+                let last = extents.last_mut().unwrap();
+                let take = gap.min(last.len);
+                compacted.push(Extent {
+                    file_id: last.file_id,
+                    start: pos,
+                    len: take,
+                });
+                pos += take;
+                last.len -= take;
+                if last.len == 0 {
+                    extents.pop();
+                }
+            } else {
+                compacted.push(ext);
+                pos = ext.start + ext.len;
+                next += 1;
+            }
+        }
+
+        self.extents = merge_adjacent(compacted);
+    }
+
+    /// Move each whole file, highest file id first, into the leftmost
+    /// free span that can hold it, if that span is to its left. Free
+    /// spans are kept in one min-heap per span length so each lookup
+    /// only checks the heap tops.
+    pub fn compact_files(&mut self) {
+        let spans = self.free_spans();
+        let max_len = spans.iter().map(|&(_, len)| len).max().unwrap_or(0);
+        let mut heaps = vec![BinaryHeap::new(); max_len + 1];
+        for (start, len) in spans {
+            heaps[len].push(Reverse(start));
+        }
+
+        let mut order = (0..self.extents.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&idx| Reverse(self.extents[idx].file_id));
+
+        for idx in order {
+            let ext = self.extents[idx];
+            let best = (ext.len..=max_len)
+                .filter_map(|len| heaps[len].peek().map(|Reverse(start)| (*start, len)))
+                .min();
+
+            let Some((start, len)) = best else { continue };
+            if start > ext.start {
+                continue;
+            }
+
+            heaps[len].pop();
+            if len > ext.len {
+                heaps[len - ext.len].push(Reverse(start + ext.len));
+            }
+            // the vacated space is right of every file still to be
+            // moved, so it never needs to go back into the heaps
+            self.extents[idx].start = start;
+        }
+
+        self.extents.sort_by_key(|ext| ext.start);
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.extents
+            .iter()
+            .map(|ext| {
+                let (start, len) = (ext.start as u64, ext.len as u64);
+                // sum of positions start..start + len
+                let position_sum = len * start + len * (len - 1) / 2;
+                position_sum * ext.file_id as u64
+            })
+            .sum()
+    }
+}
+
+fn merge_adjacent(extents: Vec<Extent>) -> Vec<Extent> {
+    let mut merged: Vec<Extent> = Vec::with_capacity(extents.len());
+    for ext in extents {
+        match merged.last_mut() {
+            Some(last) if last.file_id == ext.file_id && last.start + last.len == ext.start => {
+                last.len += ext.len
+            }
+            _ => merged.push(ext),
+        }
+    }
+    merged
+}
+
+/// Renders the block notation, e.g. `00...111...2`, using the last
+/// digit of the file id for each block and `.` for free blocks.
+impl fmt::Display for DiskMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut pos = 0;
+        for ext in self.extents.iter() {
+            write!(f, "{}", ".".repeat(ext.start - pos))?;
+            let digit = char::from_digit((ext.file_id % 10) as u32, 10).unwrap();
+            write!(f, "{}", digit.to_string().repeat(ext.len))?;
+            pos = ext.start + ext.len;
+        }
+        write!(f, "{}", ".".repeat(self.size - pos))
+    }
+}
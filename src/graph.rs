@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// An undirected graph over string node names. Names are interned to
+/// dense ids on insertion and every query works in terms of ids.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacent: Vec<HashSet<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    /// Number of nodes with each degree.
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_edges<'a>(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut graph = Self::new();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    /// Build from lines of the form `a-b`. Blank lines are skipped.
+    pub fn parse_edge_list(input: &str) -> Option<Self> {
+        let edges = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| line.split_once('-'))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::from_edges(edges))
+    }
+
+    /// Id for `name`, adding it as an isolated node if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacent.push(HashSet::new());
+        id
+    }
+
+    /// Connect `a` and `b`. A node is never its own neighbour, so an
+    /// edge from a node to itself only adds the node.
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let a = self.intern(a);
+        let b = self.intern(b);
+        if a == b {
+            return;
+        }
+        self.adjacent[a].insert(b);
+        self.adjacent[b].insert(a);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Names for a set of ids, sorted.
+    pub fn names_of(&self, ids: &[usize]) -> Vec<&str> {
+        let mut names = ids.iter().map(|&id| self.name(id)).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    pub fn neighbors(&self, id: usize) -> &HashSet<usize> {
+        &self.adjacent[id]
+    }
+
+    pub fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.adjacent[a].contains(&b)
+    }
+
+    /// Every set of three mutually connected nodes, each listed once
+    /// with ids in increasing order.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        let mut triangles = vec![];
+        for a in 0..self.len() {
+            for &b in self.adjacent[a].iter().filter(|&&b| b > a) {
+                for &c in self.adjacent[b].iter().filter(|&&c| c > b) {
+                    if self.has_edge(a, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+        triangles
+    }
+
+    /// Every clique that can't be extended by another node, found
+    /// with Bron–Kerbosch using the highest degree candidate as pivot.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            &mut vec![],
+            (0..self.len()).collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: HashSet<usize>,
+        mut excluded: HashSet<usize>,
+        cliques: &mut Vec<Vec<usize>>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            cliques.push(clique.clone());
+            return;
+        }
+
+        // any maximal clique contains the pivot or one of its
+        // non-neighbors, so only those need to be tried
+        let pivot = candidates
+            .union(&excluded)
+            .max_by_key(|&&v| self.adjacent[v].intersection(&candidates).count())
+            .copied()
+            .unwrap();
+        let to_try = candidates
+            .difference(&self.adjacent[pivot])
+            .copied()
+            .collect::<Vec<_>>();
+
+        for v in to_try {
+            let neighbors = &self.adjacent[v];
+            clique.push(v);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                cliques,
+            );
+            clique.pop();
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+
+    /// A largest clique in the graph.
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(|c| c.len())
+            .unwrap_or_default()
+    }
+
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.len()];
+        let mut components = vec![];
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut stack = vec![start];
+            let mut component = vec![];
+            while let Some(v) = stack.pop() {
+                component.push(v);
+                for &n in self.adjacent[v].iter() {
+                    if !seen[n] {
+                        seen[n] = true;
+                        stack.push(n);
+                    }
+                }
+            }
+            component.sort();
+            components.push(component);
+        }
+        components
+    }
+
+    pub fn degree_stats(&self) -> Option<DegreeStats> {
+        let degrees = self.adjacent.iter().map(HashSet::len);
+        let mut histogram = BTreeMap::new();
+        for d in degrees.clone() {
+            *histogram.entry(d).or_insert(0) += 1;
+        }
+        Some(DegreeStats {
+            min: degrees.clone().min()?,
+            max: degrees.clone().max()?,
+            mean: degrees.sum::<usize>() as f64 / self.len() as f64,
+            histogram,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_loop_ignored() {
+        let graph = Graph::parse_edge_list("a-a\na-b\nb-c\na-c").unwrap();
+        assert_eq!(graph.triangles().len(), 1);
+        assert_eq!(graph.names_of(&graph.maximum_clique()), ["a", "b", "c"]);
+    }
+}
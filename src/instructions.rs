@@ -0,0 +1,182 @@
+/// An instruction found in corrupted memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont,
+    /// Any extra instruction registered with the scanner.
+    Other {
+        name: String,
+        args: Vec<i64>,
+    },
+}
+
+/// An instruction and where it was found in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// Byte offset of the first character of the instruction name.
+    pub offset: usize,
+    /// Length in bytes, up to and including the closing parenthesis.
+    pub len: usize,
+    pub instruction: Instruction,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScannerConfig {
+    /// Fewest digits an argument may have.
+    pub min_digits: usize,
+    /// Most digits an argument may have.
+    pub max_digits: usize,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            min_digits: 1,
+            max_digits: 3,
+        }
+    }
+}
+
+/// Finds well formed `name(arg,...)` instructions in otherwise garbage
+/// input, in a single pass over the bytes.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    config: ScannerConfig,
+    /// Instruction names and the number of arguments they take.
+    instructions: Vec<(String, usize)>,
+}
+
+impl Scanner {
+    /// A scanner for `mul(a,b)`, `do()` and `don't()`.
+    pub fn new(config: ScannerConfig) -> Self {
+        Self {
+            config,
+            instructions: vec![
+                ("mul".to_string(), 2),
+                ("do".to_string(), 0),
+                ("don't".to_string(), 0),
+            ],
+        }
+    }
+
+    /// Also recognise `name` taking `arity` arguments. These are
+    /// reported as `Instruction::Other`.
+    pub fn add_instruction(&mut self, name: &str, arity: usize) {
+        self.instructions.push((name.to_string(), arity));
+    }
+
+    /// Every instruction in `input`, in order. Matching restarts after
+    /// the end of each instruction found, or one byte on after a
+    /// failed match, so no byte is looked at more than a bounded
+    /// number of times.
+    pub fn tokens(&self, input: &str) -> Vec<Token> {
+        let bytes = input.as_bytes();
+        let mut tokens = vec![];
+        let mut pos = 0;
+        while pos < bytes.len() {
+            match self.match_at(bytes, pos) {
+                Some(token) => {
+                    pos += token.len;
+                    tokens.push(token);
+                }
+                None => pos += 1,
+            }
+        }
+        tokens
+    }
+
+    fn match_at(&self, bytes: &[u8], pos: usize) -> Option<Token> {
+        self.instructions.iter().find_map(|(name, arity)| {
+            let rest = bytes[pos..].strip_prefix(name.as_bytes())?;
+            let (args, len) = self.match_args(rest, *arity)?;
+            let instruction = match (name.as_str(), args.as_slice()) {
+                ("mul", &[a, b]) => Instruction::Mul(a, b),
+                ("do", []) => Instruction::Do,
+                ("don't", []) => Instruction::Dont,
+                _ => Instruction::Other {
+                    name: name.clone(),
+                    args,
+                },
+            };
+            Some(Token {
+                offset: pos,
+                len: name.len() + len,
+                instruction,
+            })
+        })
+    }
+
+    /// Parse `(n,n,...)` with exactly `arity` numbers at the start of
+    /// `bytes`, returning them and the number of bytes used.
+    fn match_args(&self, bytes: &[u8], arity: usize) -> Option<(Vec<i64>, usize)> {
+        let mut pos = 0;
+        let expect = |b: u8, pos: &mut usize| (bytes.get(*pos) == Some(&b)).then(|| *pos += 1);
+
+        expect(b'(', &mut pos)?;
+        let mut args = Vec::with_capacity(arity);
+        for n in 0..arity {
+            if n > 0 {
+                expect(b',', &mut pos)?;
+            }
+            let digits = bytes[pos..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            if digits < self.config.min_digits || digits > self.config.max_digits {
+                return None;
+            }
+            let value = bytes[pos..pos + digits].iter().try_fold(0i64, |acc, &b| {
+                acc.checked_mul(10)?.checked_add((b - b'0') as i64)
+            })?;
+            args.push(value);
+            pos += digits;
+        }
+        expect(b')', &mut pos)?;
+
+        Some((args, pos))
+    }
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new(ScannerConfig::default())
+    }
+}
+
+/// Sum of the products of every `mul`. With `conditionals`, `don't()`
+/// switches them off until the next `do()`. Returns `None` if the sum
+/// overflows.
+pub fn evaluate(tokens: &[Token], conditionals: bool) -> Option<i64> {
+    let mut enabled = true;
+    let mut total = 0i64;
+    for token in tokens {
+        match token.instruction {
+            Instruction::Mul(a, b) if enabled => total = total.checked_add(a.checked_mul(b)?)?,
+            Instruction::Do => enabled = true,
+            Instruction::Dont if conditionals => enabled = false,
+            _ => (),
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_is_none() {
+        let scanner = Scanner::new(ScannerConfig {
+            min_digits: 1,
+            max_digits: 12,
+        });
+        let tokens = scanner.tokens("mul(999999999999,999999999999)");
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(evaluate(&tokens, false), None);
+        assert_eq!(
+            evaluate(&scanner.tokens("mul(2,4)mul(5,5)"), false),
+            Some(33)
+        );
+    }
+}
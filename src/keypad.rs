@@ -0,0 +1,191 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Key the driving keypad presses to activate the key under the arm.
+pub const ACTIVATE: char = 'A';
+
+const DIRECTIONS: [(char, (i32, i32)); 4] =
+    [('^', (-1, 0)), ('v', (1, 0)), ('<', (0, -1)), ('>', (0, 1))];
+
+/// A grid of keys with an arm that moves over them. Gaps are
+/// positions the arm is never allowed to hover over.
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    keys: HashMap<char, (i32, i32)>,
+    tiles: HashSet<(i32, i32)>,
+    paths: HashMap<(char, char), Vec<Vec<char>>>,
+}
+
+impl Keypad {
+    /// Build a keypad from a layout string, one line per row. A space
+    /// marks a gap, any other character is a key.
+    pub fn from_layout(layout: &str) -> Self {
+        let mut keys = HashMap::new();
+        for (ridx, row) in layout.lines().enumerate() {
+            for (cidx, c) in row.chars().enumerate() {
+                if c != ' ' {
+                    keys.insert(c, (ridx as i32, cidx as i32));
+                }
+            }
+        }
+        let tiles = keys.values().copied().collect();
+
+        let mut keypad = Self {
+            keys,
+            tiles,
+            paths: HashMap::new(),
+        };
+        keypad.paths = keypad
+            .keys
+            .keys()
+            .flat_map(|&s| keypad.keys.keys().map(move |&d| (s, d)))
+            .map(|(s, d)| ((s, d), keypad.search_paths(s, d)))
+            .collect();
+        keypad
+    }
+
+    /// The door keypad from the puzzle.
+    pub fn numeric() -> Self {
+        Self::from_layout("789\n456\n123\n 0A")
+    }
+
+    /// The arrow keypad from the puzzle.
+    pub fn directional() -> Self {
+        Self::from_layout(" ^A\n<v>")
+    }
+
+    pub fn contains(&self, key: char) -> bool {
+        self.keys.contains_key(&key)
+    }
+
+    /// Every shortest sequence of arrow presses moving the arm from
+    /// `from` to `to` without crossing a gap. The final activate press
+    /// is not included.
+    pub fn shortest_paths(&self, from: char, to: char) -> &[Vec<char>] {
+        self.paths.get(&(from, to)).map_or(&[], |v| v.as_slice())
+    }
+
+    fn search_paths(&self, from: char, to: char) -> Vec<Vec<char>> {
+        let start = self.keys[&from];
+        let end = self.keys[&to];
+
+        // distances back from the end so each step can be checked
+        // for making progress
+        let mut dist = HashMap::from([(end, 0)]);
+        let mut queue = VecDeque::from([end]);
+        while let Some(pos) = queue.pop_front() {
+            for (_, (dr, dc)) in DIRECTIONS {
+                let next = (pos.0 + dr, pos.1 + dc);
+                if self.tiles.contains(&next) && !dist.contains_key(&next) {
+                    dist.insert(next, dist[&pos] + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !dist.contains_key(&start) {
+            return vec![];
+        }
+
+        let mut paths = vec![];
+        let mut stack = vec![(start, vec![])];
+        while let Some((pos, path)) = stack.pop() {
+            if pos == end {
+                paths.push(path);
+                continue;
+            }
+            for (arrow, (dr, dc)) in DIRECTIONS {
+                let next = (pos.0 + dr, pos.1 + dc);
+                if dist.get(&next).is_some_and(|&d| d + 1 == dist[&pos]) {
+                    let mut path = path.clone();
+                    path.push(arrow);
+                    stack.push((next, path));
+                }
+            }
+        }
+        paths
+    }
+}
+
+/// A chain of keypads where each keypad's arm is driven by presses on
+/// the next keypad in the chain. The last keypad is pressed directly.
+#[derive(Debug)]
+pub struct KeypadChain {
+    keypads: Vec<Keypad>,
+    cache: HashMap<(usize, char, char), Option<u64>>,
+}
+
+impl KeypadChain {
+    /// `keypads[0]` is the keypad the code is typed on. Every later
+    /// keypad must contain the arrow keys and the activate key.
+    pub fn new(keypads: Vec<Keypad>) -> Self {
+        assert!(!keypads.is_empty());
+        for keypad in keypads.iter().skip(1) {
+            assert!(DIRECTIONS.iter().all(|&(c, _)| keypad.contains(c)));
+            assert!(keypad.contains(ACTIVATE));
+        }
+
+        Self {
+            keypads,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// The puzzle's setup: a numeric keypad, `robots` robot operated
+    /// arrow keypads, and the arrow keypad pressed by hand.
+    pub fn door(robots: usize) -> Self {
+        let mut keypads = vec![Keypad::numeric()];
+        keypads.extend(std::iter::repeat_n(Keypad::directional(), robots + 1));
+        Self::new(keypads)
+    }
+
+    /// Fewest presses on the final keypad needed to type `code` on the
+    /// first. Every arm starts on the activate key. Returns `None` if
+    /// a key is missing or unreachable.
+    pub fn cost(&mut self, code: &str) -> Option<u64> {
+        let code = code.chars().collect::<Vec<_>>();
+        self.sequence_cost(0, &code)
+    }
+
+    fn sequence_cost(&mut self, level: usize, keys: &[char]) -> Option<u64> {
+        if level == self.keypads.len() - 1 {
+            return keys
+                .iter()
+                .all(|&k| self.keypads[level].contains(k))
+                .then_some(keys.len() as u64);
+        }
+
+        let mut prev = ACTIVATE;
+        let mut total = 0;
+        for &key in keys {
+            total += self.move_cost(level, prev, key)?;
+            prev = key;
+        }
+        Some(total)
+    }
+
+    /// Presses on the final keypad to move the arm at `level` from
+    /// `from` to `to` and activate it, leaving every later arm back on
+    /// the activate key.
+    fn move_cost(&mut self, level: usize, from: char, to: char) -> Option<u64> {
+        if let Some(&cost) = self.cache.get(&(level, from, to)) {
+            return cost;
+        }
+
+        let keypad = &self.keypads[level];
+        let cost = if !keypad.contains(from) || !keypad.contains(to) {
+            None
+        } else {
+            let paths = keypad.shortest_paths(from, to).to_vec();
+            paths
+                .into_iter()
+                .filter_map(|mut path| {
+                    path.push(ACTIVATE);
+                    self.sequence_cost(level + 1, &path)
+                })
+                .min()
+        };
+
+        self.cache.insert((level, from, to), cost);
+        cost
+    }
+}
@@ -4,7 +4,31 @@ use std::{
     path::{Path, PathBuf},
 };
 
+pub mod antennas;
+pub mod bitmap;
+pub mod circuit;
+pub mod connectivity;
 pub mod dijkstra;
+pub mod disk;
+pub mod graph;
+pub mod instructions;
+pub mod keypad;
+pub mod linear;
+pub mod locations;
+pub mod operators;
+pub mod patrol;
+pub mod precedence;
+pub mod racetrack;
+pub mod regions;
+pub mod reports;
+pub mod schematic;
+pub mod secret;
+pub mod stones;
+pub mod torus;
+pub mod towels;
+pub mod trails;
+pub mod warehouse;
+pub mod wordsearch;
 
 use clap::Parser;
 
@@ -34,7 +58,7 @@ pub fn read_line() -> io::Result<()> {
     Ok(())
 }
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 pub struct Args {
     #[arg(short, long)]
     pub file: Option<PathBuf>,
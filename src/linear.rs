@@ -0,0 +1,351 @@
+use std::fmt::Display;
+
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+/// Integer solutions of `A x = b`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegerSolution {
+    /// No integer vector satisfies the system.
+    None,
+    /// Exactly one integer vector satisfies the system.
+    Unique(Vec<BigInt>),
+    /// Every solution is `base + sum(t_k * directions[k])` for integer
+    /// `t_k`.
+    Parametric {
+        base: Vec<BigInt>,
+        directions: Vec<Vec<BigInt>>,
+    },
+}
+
+/// Convenience for building a matrix out of small integers.
+pub fn matrix(rows: &[&[i64]]) -> Vec<Vec<BigInt>> {
+    rows.iter()
+        .map(|row| row.iter().map(|&v| BigInt::from(v)).collect())
+        .collect()
+}
+
+/// Solve a square system over the rationals with fraction free
+/// (Bareiss) elimination. Returns `None` if the matrix is singular.
+pub fn solve_rational(a: &[Vec<BigInt>], b: &[BigInt]) -> Option<Vec<BigRational>> {
+    let n = a.len();
+    assert!(a.iter().all(|row| row.len() == n) && b.len() == n);
+
+    let mut m = a
+        .iter()
+        .zip(b)
+        .map(|(row, v)| {
+            let mut row = row.clone();
+            row.push(v.clone());
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut prev_pivot = BigInt::one();
+    for k in 0..n {
+        let pivot_row = (k..n).find(|&r| !m[r][k].is_zero())?;
+        m.swap(k, pivot_row);
+
+        for i in k + 1..n {
+            for j in k + 1..=n {
+                // exact by Sylvester's identity
+                m[i][j] = (&m[i][j] * &m[k][k] - &m[i][k] * &m[k][j]) / &prev_pivot;
+            }
+            m[i][k] = BigInt::zero();
+        }
+        prev_pivot = m[k][k].clone();
+    }
+
+    let mut x = vec![BigRational::zero(); n];
+    for i in (0..n).rev() {
+        let rhs = (i + 1..n).fold(BigRational::from(m[i][n].clone()), |acc, j| {
+            acc - BigRational::from(m[i][j].clone()) * &x[j]
+        });
+        x[i] = rhs / BigRational::from(m[i][i].clone());
+    }
+    Some(x)
+}
+
+/// Find every integer solution of `A x = b`, including for singular
+/// or non-square systems. Columns are reduced to echelon form with
+/// unimodular extended GCD steps, so the transform's trailing columns
+/// span the integer null space.
+pub fn solve_integer(a: &[Vec<BigInt>], b: &[BigInt]) -> IntegerSolution {
+    let rows = a.len();
+    let cols = a.first().map_or(0, |row| row.len());
+    assert!(a.iter().all(|row| row.len() == cols) && b.len() == rows);
+
+    let mut h = a.to_vec();
+    let mut u = (0..cols)
+        .map(|i| {
+            (0..cols)
+                .map(|j| {
+                    if i == j {
+                        BigInt::one()
+                    } else {
+                        BigInt::zero()
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // pivot rows in order, pivot k lives in column k
+    let mut pivots = vec![];
+    for i in 0..rows {
+        let c = pivots.len();
+        if c == cols {
+            break;
+        }
+        for j in c + 1..cols {
+            if h[i][j].is_zero() {
+                continue;
+            }
+            let (p, q) = (h[i][c].clone(), h[i][j].clone());
+            let egcd = p.extended_gcd(&q);
+            let (pg, qg) = (&p / &egcd.gcd, &q / &egcd.gcd);
+            // [s -q/g; t p/g] has determinant 1
+            combine_columns(&mut h, c, j, &egcd.x, &egcd.y, &qg, &pg);
+            combine_columns(&mut u, c, j, &egcd.x, &egcd.y, &qg, &pg);
+        }
+        if !h[i][c].is_zero() {
+            pivots.push(i);
+        }
+    }
+
+    let rank = pivots.len();
+    let mut y = vec![BigInt::zero(); cols];
+    for (k, &i) in pivots.iter().enumerate() {
+        let rhs = (0..k).fold(b[i].clone(), |acc, j| acc - &h[i][j] * &y[j]);
+        let (q, r) = rhs.div_rem(&h[i][k]);
+        if !r.is_zero() {
+            return IntegerSolution::None;
+        }
+        y[k] = q;
+    }
+
+    // rows that didn't produce a pivot must already be satisfied
+    for i in 0..rows {
+        let lhs = (0..rank).fold(BigInt::zero(), |acc, j| acc + &h[i][j] * &y[j]);
+        if lhs != b[i] {
+            return IntegerSolution::None;
+        }
+    }
+
+    let base = (0..cols)
+        .map(|r| (0..rank).fold(BigInt::zero(), |acc, k| acc + &u[r][k] * &y[k]))
+        .collect::<Vec<_>>();
+    if rank == cols {
+        return IntegerSolution::Unique(base);
+    }
+
+    let directions = (rank..cols)
+        .map(|k| (0..cols).map(|r| u[r][k].clone()).collect())
+        .collect();
+    IntegerSolution::Parametric { base, directions }
+}
+
+/// col_c, col_j = s*col_c + t*col_j, -qg*col_c + pg*col_j
+fn combine_columns(
+    m: &mut [Vec<BigInt>],
+    c: usize,
+    j: usize,
+    s: &BigInt,
+    t: &BigInt,
+    qg: &BigInt,
+    pg: &BigInt,
+) {
+    for row in m.iter_mut() {
+        let (vc, vj) = (row[c].clone(), row[j].clone());
+        row[c] = s * &vc + t * &vj;
+        row[j] = pg * &vj - qg * &vc;
+    }
+}
+
+/// A system [`min_cost_solution`] can't search, with this many free
+/// parameters left once unused variables are taken out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub free: usize,
+}
+
+impl Display for Unsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "minimising over {} free parameters is not supported",
+            self.free
+        )
+    }
+}
+
+impl std::error::Error for Unsupported {}
+
+/// Cheapest solution of `A x = b` with every `x_i` in
+/// `0..=upper_bound` (unbounded above if `None`), where the cost is
+/// `cost . x`. Returns the solution and its cost, or `None` if there
+/// is no solution or the cost has no minimum.
+///
+/// A variable whose column of `A` is all zero is set on its own to
+/// whichever bound is cheapest. What is left may have at most one
+/// free parameter; systems with more are an error rather than being
+/// searched.
+pub fn min_cost_solution(
+    a: &[Vec<BigInt>],
+    b: &[BigInt],
+    cost: &[BigInt],
+    upper_bound: Option<&BigInt>,
+) -> Result<Option<(Vec<BigInt>, BigInt)>, Unsupported> {
+    let cols = a.first().map_or(0, |row| row.len());
+    let unused = (0..cols)
+        .map(|j| a.iter().all(|row| row[j].is_zero()))
+        .collect::<Vec<_>>();
+    if !unused.contains(&true) {
+        return min_cost_used(a, b, cost, upper_bound);
+    }
+
+    let used = (0..cols).filter(|&j| !unused[j]).collect::<Vec<_>>();
+    let a_used = a
+        .iter()
+        .map(|row| used.iter().map(|&j| row[j].clone()).collect())
+        .collect::<Vec<_>>();
+    let cost_used = used.iter().map(|&j| cost[j].clone()).collect::<Vec<_>>();
+    let Some((x_used, mut total)) = min_cost_used(&a_used, b, &cost_used, upper_bound)? else {
+        return Ok(None);
+    };
+
+    let mut x = vec![BigInt::zero(); cols];
+    for (&j, v) in used.iter().zip(x_used) {
+        x[j] = v;
+    }
+    for j in (0..cols).filter(|&j| unused[j] && cost[j].is_negative()) {
+        let Some(ub) = upper_bound else {
+            return Ok(None);
+        };
+        x[j] = ub.clone();
+        total += &cost[j] * ub;
+    }
+    Ok(Some((x, total)))
+}
+
+/// [`min_cost_solution`] for a system where every variable is used.
+fn min_cost_used(
+    a: &[Vec<BigInt>],
+    b: &[BigInt],
+    cost: &[BigInt],
+    upper_bound: Option<&BigInt>,
+) -> Result<Option<(Vec<BigInt>, BigInt)>, Unsupported> {
+    let in_bounds = |v: &BigInt| !v.is_negative() && upper_bound.is_none_or(|ub| v <= ub);
+    let dot = |x: &[BigInt]| {
+        x.iter()
+            .zip(cost)
+            .fold(BigInt::zero(), |acc, (v, c)| acc + v * c)
+    };
+
+    Ok(match solve_integer(a, b) {
+        IntegerSolution::None => None,
+        IntegerSolution::Unique(x) => x.iter().all(in_bounds).then(|| {
+            let total = dot(&x);
+            (x, total)
+        }),
+        IntegerSolution::Parametric { base, directions } => 'search: {
+            let [d] = directions.as_slice() else {
+                return Err(Unsupported {
+                    free: directions.len(),
+                });
+            };
+
+            // x = base + t*d, narrow t so every x_i stays in bounds
+            let mut lo: Option<BigInt> = None;
+            let mut hi: Option<BigInt> = None;
+            // record t*step >= rhs
+            let mut require = |rhs: BigInt, step: &BigInt| {
+                if step.is_positive() {
+                    let t = rhs.div_ceil(step);
+                    lo = Some(lo.take().map_or(t.clone(), |v| v.max(t)));
+                } else {
+                    let t = rhs.div_floor(step);
+                    hi = Some(hi.take().map_or(t.clone(), |v| v.min(t)));
+                }
+            };
+
+            for (value, step) in base.iter().zip(d) {
+                if step.is_zero() {
+                    if !in_bounds(value) {
+                        break 'search None;
+                    }
+                    continue;
+                }
+                require(-value, step);
+                if let Some(ub) = upper_bound {
+                    require(value - ub, &-step);
+                }
+            }
+
+            if let (Some(lo), Some(hi)) = (&lo, &hi) {
+                if lo > hi {
+                    break 'search None;
+                }
+            }
+
+            let slope = dot(d);
+            let t = if slope.is_positive() {
+                lo
+            } else if slope.is_negative() {
+                hi
+            } else {
+                Some(lo.or(hi).unwrap_or_else(BigInt::zero))
+            };
+            let Some(t) = t else {
+                break 'search None;
+            };
+
+            let x = base
+                .iter()
+                .zip(d)
+                .map(|(v, s)| v + &t * s)
+                .collect::<Vec<_>>();
+            let total = dot(&x);
+            Some((x, total))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_variables() {
+        let zero = matrix(&[&[0, 0], &[0, 0]]);
+        let cost = [BigInt::from(3), BigInt::from(1)];
+        let b = [BigInt::zero(), BigInt::zero()];
+        assert_eq!(
+            min_cost_solution(&zero, &b, &cost, None),
+            Ok(Some((vec![BigInt::zero(), BigInt::zero()], BigInt::zero())))
+        );
+        let b = [BigInt::one(), BigInt::zero()];
+        assert_eq!(min_cost_solution(&zero, &b, &cost, None), Ok(None));
+
+        // x_0 is unused, x_1 is pinned at 2
+        let a = matrix(&[&[0, 3], &[0, 1]]);
+        let b = [BigInt::from(6), BigInt::from(2)];
+        assert_eq!(
+            min_cost_solution(&a, &b, &cost, None),
+            Ok(Some((
+                vec![BigInt::zero(), BigInt::from(2)],
+                BigInt::from(2)
+            )))
+        );
+    }
+
+    #[test]
+    fn too_many_parameters() {
+        let a = matrix(&[&[1, 1, 1]]);
+        let b = [BigInt::from(5)];
+        let cost = [BigInt::one(), BigInt::one(), BigInt::one()];
+        assert_eq!(
+            min_cost_solution(&a, &b, &cost, None),
+            Err(Unsupported { free: 2 })
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::{self, BufRead},
+    iter::repeat_n,
+};
+
+use itertools::{EitherOrBoth, Itertools};
+
+#[derive(Debug)]
+pub enum LocationError {
+    Io(io::Error),
+    /// A line that isn't one or two location ids, numbered from 1.
+    Parse {
+        line: usize,
+        text: String,
+    },
+}
+
+impl Display for LocationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LocationError::Io(err) => write!(f, "{err}"),
+            LocationError::Parse { line, text } => {
+                write!(f, "line {line} is not a pair of location ids: {text:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LocationError {}
+
+impl From<io::Error> for LocationError {
+    fn from(err: io::Error) -> Self {
+        LocationError::Io(err)
+    }
+}
+
+/// Every id, repeated as often as it appears, in order.
+fn expand(counts: &BTreeMap<i64, u64>) -> impl Iterator<Item = i64> + '_ {
+    counts
+        .iter()
+        .flat_map(|(&id, &count)| repeat_n(id, count as usize))
+}
+
+/// Distance and similarity between two lists, and how well they lined
+/// up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Sum of the differences between the paired ids.
+    pub distance: u64,
+    /// Sum of each left id times how often it is in the right list.
+    pub similarity: i64,
+    pub paired: u64,
+    /// Ids left over at the end of the longer list.
+    pub unpaired_left: u64,
+    pub unpaired_right: u64,
+}
+
+/// Two lists of location ids. Only how many times each id appears is
+/// kept, already in order, so memory grows with the number of distinct
+/// ids rather than the length of the lists.
+#[derive(Debug, Clone, Default)]
+pub struct LocationLists {
+    left: BTreeMap<i64, u64>,
+    right: BTreeMap<i64, u64>,
+}
+
+impl LocationLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_left(&mut self, id: i64) {
+        *self.left.entry(id).or_insert(0) += 1;
+    }
+
+    pub fn push_right(&mut self, id: i64) {
+        *self.right.entry(id).or_insert(0) += 1;
+    }
+
+    /// Read the lists a line at a time. A line holds a left and a right
+    /// id; a single id is on the left unless the line is indented,
+    /// which puts it on the right, so the lists can differ in length.
+    pub fn read(reader: impl BufRead) -> Result<Self, LocationError> {
+        let mut lists = Self::new();
+        for (n, line) in reader.lines().enumerate() {
+            let line = line?;
+            let err = || LocationError::Parse {
+                line: n + 1,
+                text: line.clone(),
+            };
+            let ids = line
+                .split_whitespace()
+                .map(|s| s.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| err())?;
+            match ids[..] {
+                [] => (),
+                [left, right] => {
+                    lists.push_left(left);
+                    lists.push_right(right);
+                }
+                [id] if line.starts_with(char::is_whitespace) => lists.push_right(id),
+                [id] => lists.push_left(id),
+                _ => return Err(err()),
+            }
+        }
+        Ok(lists)
+    }
+
+    pub fn left_len(&self) -> u64 {
+        self.left.values().sum()
+    }
+
+    pub fn right_len(&self) -> u64 {
+        self.right.values().sum()
+    }
+
+    /// The smallest id on the left paired with the smallest on the
+    /// right and so on, with whatever is left of the longer list at the
+    /// end.
+    pub fn pairs(&self) -> impl Iterator<Item = EitherOrBoth<i64, i64>> + '_ {
+        expand(&self.left).zip_longest(expand(&self.right))
+    }
+
+    pub fn distance(&self) -> u64 {
+        self.pairs()
+            .filter_map(|pair| pair.both())
+            .map(|(a, b)| a.abs_diff(b))
+            .sum()
+    }
+
+    pub fn similarity(&self) -> i64 {
+        self.left
+            .iter()
+            .map(|(&id, &count)| {
+                let matches = self.right.get(&id).copied().unwrap_or(0);
+                id * count as i64 * matches as i64
+            })
+            .sum()
+    }
+
+    pub fn reconcile(&self) -> Reconciliation {
+        let (left, right) = (self.left_len(), self.right_len());
+        Reconciliation {
+            distance: self.distance(),
+            similarity: self.similarity(),
+            paired: left.min(right),
+            unpaired_left: left.saturating_sub(right),
+            unpaired_right: right.saturating_sub(left),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use rayon::prelude::*;
+
+/// A binary operator for equations evaluated strictly left to right.
+/// Operands are assumed to be non-negative.
+pub trait Operator: Send + Sync {
+    fn symbol(&self) -> &str;
+
+    /// `lhs op rhs`, or `None` on overflow or if undefined.
+    fn apply(&self, lhs: i64, rhs: i64) -> Option<i64>;
+
+    /// The `lhs` for which `lhs op rhs == result`, if there is one.
+    /// Used to prune the search from the right hand end.
+    fn invert(&self, result: i64, rhs: i64) -> Option<i64>;
+
+    /// Whether `lhs op rhs == result` for every `lhs`, like `x * 0 == 0`.
+    /// `invert` can't express that, so the search then only needs the
+    /// numbers to the left to evaluate to anything at all.
+    fn absorbs(&self, _result: i64, _rhs: i64) -> bool {
+        false
+    }
+}
+
+pub struct Add;
+
+impl Operator for Add {
+    fn symbol(&self) -> &str {
+        "+"
+    }
+
+    fn apply(&self, lhs: i64, rhs: i64) -> Option<i64> {
+        lhs.checked_add(rhs)
+    }
+
+    fn invert(&self, result: i64, rhs: i64) -> Option<i64> {
+        (result >= rhs).then(|| result - rhs)
+    }
+}
+
+pub struct Mul;
+
+impl Operator for Mul {
+    fn symbol(&self) -> &str {
+        "*"
+    }
+
+    fn apply(&self, lhs: i64, rhs: i64) -> Option<i64> {
+        lhs.checked_mul(rhs)
+    }
+
+    fn invert(&self, result: i64, rhs: i64) -> Option<i64> {
+        (rhs != 0 && result % rhs == 0).then(|| result / rhs)
+    }
+
+    fn absorbs(&self, result: i64, rhs: i64) -> bool {
+        rhs == 0 && result == 0
+    }
+}
+
+/// Joins the decimal digits, `12 || 345 = 12345`.
+pub struct Concat;
+
+impl Concat {
+    fn shift(rhs: i64) -> i64 {
+        let mut shift = 10;
+        while shift <= rhs {
+            shift *= 10;
+        }
+        shift
+    }
+}
+
+impl Operator for Concat {
+    fn symbol(&self) -> &str {
+        "||"
+    }
+
+    fn apply(&self, lhs: i64, rhs: i64) -> Option<i64> {
+        lhs.checked_mul(Self::shift(rhs))?.checked_add(rhs)
+    }
+
+    fn invert(&self, result: i64, rhs: i64) -> Option<i64> {
+        let shift = Self::shift(rhs);
+        (result % shift == rhs).then(|| result / shift)
+    }
+}
+
+/// Searches for operators to place between numbers to reach a target.
+pub struct OperatorSolver {
+    operators: Vec<Box<dyn Operator>>,
+}
+
+impl OperatorSolver {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Self {
+        Self { operators }
+    }
+
+    /// Operators tried in order for every gap.
+    pub fn operators(&self) -> &[Box<dyn Operator>] {
+        &self.operators
+    }
+
+    /// Operators to put between `nums`, left to right, so they
+    /// evaluate to `target`. The search starts from the last number
+    /// and only follows operators that can be inverted, so most
+    /// branches are cut off without evaluating the left side.
+    pub fn solve(&self, target: i64, nums: &[i64]) -> Option<Vec<&dyn Operator>> {
+        let mut ops = Vec::with_capacity(nums.len().saturating_sub(1));
+        self.solve_aux(target, nums, &mut ops).then(|| {
+            ops.reverse();
+            ops
+        })
+    }
+
+    fn solve_aux<'a>(&'a self, target: i64, nums: &[i64], ops: &mut Vec<&'a dyn Operator>) -> bool {
+        match nums {
+            [] => false,
+            [first] => *first == target,
+            [rest @ .., last] => self.operators.iter().any(|op| {
+                ops.push(op.as_ref());
+                let found = if op.absorbs(target, *last) {
+                    let len = ops.len();
+                    let found = self.evaluate_any(rest[0], &rest[1..], ops);
+                    // found forwards, but `ops` is built from the right
+                    ops[len..].reverse();
+                    found
+                } else {
+                    op.invert(target, *last)
+                        .is_some_and(|lhs| self.solve_aux(lhs, rest, ops))
+                };
+                if !found {
+                    ops.pop();
+                }
+                found
+            }),
+        }
+    }
+
+    /// Any operators that evaluate `acc` followed by `nums` without
+    /// overflowing, pushed left to right.
+    fn evaluate_any<'a>(&'a self, acc: i64, nums: &[i64], ops: &mut Vec<&'a dyn Operator>) -> bool {
+        let Some((next, rest)) = nums.split_first() else {
+            return true;
+        };
+        self.operators.iter().any(|op| {
+            let Some(acc) = op.apply(acc, *next) else {
+                return false;
+            };
+            ops.push(op.as_ref());
+            let found = self.evaluate_any(acc, rest, ops);
+            if !found {
+                ops.pop();
+            }
+            found
+        })
+    }
+
+    /// Solve every `(target, nums)` equation in parallel.
+    pub fn solve_all(&self, equations: &[(i64, Vec<i64>)]) -> Vec<Option<Vec<&dyn Operator>>> {
+        equations
+            .par_iter()
+            .map(|(target, nums)| self.solve(*target, nums))
+            .collect()
+    }
+
+    /// Evaluate `nums` joined by `ops` left to right.
+    pub fn evaluate(nums: &[i64], ops: &[&dyn Operator]) -> Option<i64> {
+        let (first, rest) = nums.split_first()?;
+        rest.iter()
+            .zip(ops)
+            .try_fold(*first, |acc, (n, op)| op.apply(acc, *n))
+    }
+
+    /// Render as e.g. `81 + 40 * 27`.
+    pub fn format(nums: &[i64], ops: &[&dyn Operator]) -> String {
+        let mut s = nums.first().map_or(String::new(), |n| n.to_string());
+        for (n, op) in nums.iter().skip(1).zip(ops) {
+            s.push_str(&format!(" {} {}", op.symbol(), n));
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(solver: &OperatorSolver, target: i64, nums: &[i64]) -> bool {
+        solver
+            .solve(target, nums)
+            .is_some_and(|ops| OperatorSolver::evaluate(nums, &ops) == Some(target))
+    }
+
+    #[test]
+    fn zero_operand() {
+        let solver = OperatorSolver::new(vec![Box::new(Add), Box::new(Mul)]);
+        assert!(solved(&solver, 0, &[3, 0]));
+        assert!(solved(&solver, 0, &[3, 0, 0]));
+        assert!(solved(&solver, 0, &[7, 5, 0]));
+        assert!(solved(&solver, 4, &[3, 0, 4]));
+        assert!(!solved(&solver, 1, &[3, 0]));
+    }
+}
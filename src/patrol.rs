@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Facing {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Facing {
+    pub fn turn_right(self) -> Self {
+        match self {
+            Facing::Up => Facing::Right,
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+        }
+    }
+
+    fn delta(self) -> (i32, i32) {
+        match self {
+            Facing::Up => (-1, 0),
+            Facing::Right => (0, 1),
+            Facing::Down => (1, 0),
+            Facing::Left => (0, -1),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Position `(row, col)` and direction of the guard.
+pub type Guard = ((usize, usize), Facing);
+
+/// A candidate obstacle that traps the guard in a loop.
+#[derive(Debug, Clone)]
+pub struct LoopCandidate {
+    pub obstacle: (usize, usize),
+    /// The states where the guard turns, in order, once inside the
+    /// loop. Following them wraps back around to the first.
+    pub loop_path: Vec<Guard>,
+}
+
+/// A map of obstacles with precomputed "where does the guard stop"
+/// tables, so a walk costs one lookup per turn rather than per cell.
+#[derive(Debug, Clone)]
+pub struct PatrolMap {
+    width: usize,
+    height: usize,
+    blocked: Vec<bool>,
+    /// For each cell and facing, the cell the guard stops on before
+    /// hitting an obstacle, or `None` if it walks off the map.
+    stops: Vec<[Option<(usize, usize)>; 4]>,
+}
+
+impl PatrolMap {
+    /// `blocked` is indexed `[row][col]`.
+    pub fn new(blocked: &[Vec<bool>]) -> Self {
+        let height = blocked.len();
+        let width = blocked.first().map_or(0, |row| row.len());
+        let blocked = blocked.concat();
+
+        let mut map = Self {
+            width,
+            height,
+            blocked,
+            stops: vec![[None; 4]; width * height],
+        };
+
+        // each facing fills its table starting from the side the guard
+        // is walking towards, reusing the neighbour's answer
+        for facing in [Facing::Up, Facing::Right, Facing::Down, Facing::Left] {
+            let rows = (0..height).collect::<Vec<_>>();
+            let cols = (0..width).collect::<Vec<_>>();
+            let (rows, cols) = match facing {
+                Facing::Up | Facing::Left => (rows, cols),
+                Facing::Down | Facing::Right => (
+                    rows.into_iter().rev().collect(),
+                    cols.into_iter().rev().collect(),
+                ),
+            };
+            for &r in rows.iter() {
+                for &c in cols.iter() {
+                    if map.is_blocked((r, c)) {
+                        continue;
+                    }
+                    let stop = match map.ahead((r, c), facing) {
+                        None => None,
+                        Some(next) if map.is_blocked(next) => Some((r, c)),
+                        Some(next) => map.stops[map.index(next)][facing.index()],
+                    };
+                    let idx = map.index((r, c));
+                    map.stops[idx][facing.index()] = stop;
+                }
+            }
+        }
+
+        map
+    }
+
+    fn index(&self, (r, c): (usize, usize)) -> usize {
+        r * self.width + c
+    }
+
+    pub fn is_blocked(&self, pos: (usize, usize)) -> bool {
+        self.blocked[self.index(pos)]
+    }
+
+    /// The cell in front of `pos`, if it is on the map.
+    fn ahead(&self, (r, c): (usize, usize), facing: Facing) -> Option<(usize, usize)> {
+        let (dr, dc) = facing.delta();
+        let (r, c) = (r as i32 + dr, c as i32 + dc);
+        (r >= 0 && c >= 0 && (r as usize) < self.height && (c as usize) < self.width)
+            .then_some((r as usize, c as usize))
+    }
+
+    /// Every state of the guard, one per move or turn, until it leaves
+    /// the map. Returns `None` if the guard loops forever.
+    pub fn walk(&self, start: Guard) -> Option<Vec<Guard>> {
+        let mut seen = HashSet::from([start]);
+        let mut states = vec![start];
+        let (mut pos, mut facing) = start;
+        while let Some(next) = self.ahead(pos, facing) {
+            if self.is_blocked(next) {
+                facing = facing.turn_right();
+            } else {
+                pos = next;
+            }
+            if !seen.insert((pos, facing)) {
+                return None;
+            }
+            states.push((pos, facing));
+        }
+        Some(states)
+    }
+
+    /// Distinct cells the guard stands on before leaving the map, in
+    /// order of first visit.
+    pub fn visited_cells(&self, start: Guard) -> Option<Vec<(usize, usize)>> {
+        let mut seen = HashSet::new();
+        Some(
+            self.walk(start)?
+                .into_iter()
+                .map(|(pos, _)| pos)
+                .filter(|&pos| seen.insert(pos))
+                .collect(),
+        )
+    }
+
+    /// Jump from turn to turn with one extra obstacle. Returns the
+    /// turning states of the loop the guard ends up in, or `None` if it
+    /// leaves the map.
+    fn find_loop(&self, start: Guard, obstacle: (usize, usize)) -> Option<Vec<Guard>> {
+        let mut seen = HashSet::new();
+        let mut turns = vec![];
+        let (mut pos, mut facing) = start;
+        loop {
+            let mut stop = self.stops[self.index(pos)][facing.index()];
+
+            // the extra obstacle wins if it is ahead and nearer than
+            // the usual stop
+            let dist = |to: (usize, usize)| pos.0.abs_diff(to.0) + pos.1.abs_diff(to.1);
+            let (dr, dc) = facing.delta();
+            let in_line = if dr == 0 {
+                obstacle.0 == pos.0 && (obstacle.1 as i32 - pos.1 as i32).signum() == dc
+            } else {
+                obstacle.1 == pos.1 && (obstacle.0 as i32 - pos.0 as i32).signum() == dr
+            };
+            if in_line && stop.is_none_or(|s| dist(s) >= dist(obstacle)) {
+                let back = (obstacle.0 as i32 - dr, obstacle.1 as i32 - dc);
+                stop = Some((back.0 as usize, back.1 as usize));
+            }
+
+            let state = (stop?, facing.turn_right());
+            if !seen.insert(state) {
+                let first = turns.iter().position(|&s| s == state).unwrap();
+                return Some(turns.split_off(first));
+            }
+            turns.push(state);
+            (pos, facing) = state;
+        }
+    }
+
+    /// Every single extra obstacle that traps the guard in a loop.
+    /// Only cells on the original path can change the walk, and each
+    /// trial starts from the state just before the guard first runs
+    /// into the new obstacle.
+    pub fn loop_obstacles(&self, start: Guard) -> Vec<LoopCandidate> {
+        let Some(states) = self.walk(start) else {
+            return vec![];
+        };
+
+        let mut tried = HashSet::from([start.0]);
+        let mut candidates = vec![];
+        for &(pos, facing) in states.iter() {
+            let Some(next) = self.ahead(pos, facing) else {
+                continue;
+            };
+            if self.is_blocked(next) || !tried.insert(next) {
+                continue;
+            }
+            if let Some(loop_path) = self.find_loop((pos, facing), next) {
+                candidates.push(LoopCandidate {
+                    obstacle: next,
+                    loop_path,
+                });
+            }
+        }
+        candidates
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+/// A set of `before|after` rules. The rules need not form a total or
+/// even an acyclic order; queries only look at the rules between the
+/// items they are given.
+#[derive(Debug, Clone)]
+pub struct Precedence<T> {
+    after: HashMap<T, HashSet<T>>,
+}
+
+impl<T> Precedence<T>
+where
+    T: Copy + Eq + Hash + Ord,
+{
+    pub fn new(rules: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut after: HashMap<T, HashSet<T>> = HashMap::new();
+        for (a, b) in rules {
+            after.entry(a).or_default().insert(b);
+        }
+        Self { after }
+    }
+
+    /// True if a rule says `a` must come before `b`.
+    pub fn must_precede(&self, a: T, b: T) -> bool {
+        self.after.get(&a).is_some_and(|s| s.contains(&b))
+    }
+
+    /// The first pair of positions `(i, j)` with `i < j` where a rule
+    /// says `seq[j]` must come before `seq[i]`, scanning by `j` and
+    /// then by `i`.
+    pub fn first_violation(&self, seq: &[T]) -> Option<(usize, usize)> {
+        (0..seq.len()).find_map(|j| {
+            (0..j)
+                .find(|&i| self.must_precede(seq[j], seq[i]))
+                .map(|i| (i, j))
+        })
+    }
+
+    pub fn is_ordered(&self, seq: &[T]) -> bool {
+        self.first_violation(seq).is_none()
+    }
+
+    /// Order `items` so every rule between them is respected, using
+    /// Kahn's algorithm on only the rules among `items`. Ties are
+    /// broken by taking the smallest ready item. Repeated items are
+    /// kept together, so the result is as long as `items`. If the rules
+    /// among the items are cyclic the cycle is returned as the error.
+    pub fn topological_sort(&self, items: &[T]) -> Result<Vec<T>, Vec<T>> {
+        let mut copies: HashMap<T, usize> = HashMap::new();
+        for &v in items {
+            *copies.entry(v).or_insert(0) += 1;
+        }
+        let subset: HashSet<T> = copies.keys().copied().collect();
+        let mut in_degree: HashMap<T, usize> = subset.iter().map(|&v| (v, 0)).collect();
+        for v in subset.iter() {
+            for w in self.successors(*v, &subset) {
+                *in_degree.get_mut(&w).unwrap() += 1;
+            }
+        }
+
+        let mut ready = in_degree
+            .iter()
+            .filter(|(_, &d)| d == 0)
+            .map(|(&v, _)| Reverse(v))
+            .collect::<BinaryHeap<_>>();
+
+        let mut sorted = Vec::with_capacity(items.len());
+        let mut placed = HashSet::with_capacity(subset.len());
+        while let Some(Reverse(v)) = ready.pop() {
+            sorted.extend(std::iter::repeat_n(v, copies[&v]));
+            placed.insert(v);
+            for w in self.successors(v, &subset) {
+                let d = in_degree.get_mut(&w).unwrap();
+                *d -= 1;
+                if *d == 0 {
+                    ready.push(Reverse(w));
+                }
+            }
+        }
+
+        if placed.len() == subset.len() {
+            Ok(sorted)
+        } else {
+            // everything left over sits on or behind a cycle
+            let remaining = subset.difference(&placed).copied().collect::<Vec<_>>();
+            Err(self.find_cycle(&remaining).unwrap())
+        }
+    }
+
+    /// A cycle among the rules between `items`, listed in rule order
+    /// with the first item not repeated at the end.
+    pub fn find_cycle(&self, items: &[T]) -> Option<Vec<T>> {
+        #[derive(Copy, Clone, PartialEq, Eq)]
+        enum Mark {
+            Unvisited,
+            OnStack,
+            Done,
+        }
+
+        let subset: HashSet<T> = items.iter().copied().collect();
+        let mut marks: HashMap<T, Mark> = subset.iter().map(|&v| (v, Mark::Unvisited)).collect();
+
+        let mut starts = subset.iter().copied().collect::<Vec<_>>();
+        starts.sort();
+
+        for start in starts {
+            if marks[&start] != Mark::Unvisited {
+                continue;
+            }
+
+            // iterative depth first search keeping the current path
+            let mut path = vec![start];
+            let mut pending = vec![self.successors(start, &subset)];
+            marks.insert(start, Mark::OnStack);
+
+            while let Some(next) = pending.last_mut() {
+                match next.pop() {
+                    Some(w) => match marks[&w] {
+                        Mark::OnStack => {
+                            let pos = path.iter().position(|&v| v == w).unwrap();
+                            return Some(path[pos..].to_vec());
+                        }
+                        Mark::Unvisited => {
+                            marks.insert(w, Mark::OnStack);
+                            path.push(w);
+                            pending.push(self.successors(w, &subset));
+                        }
+                        Mark::Done => (),
+                    },
+                    None => {
+                        marks.insert(path.pop().unwrap(), Mark::Done);
+                        pending.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Items in `subset` that must come after `v`, sorted descending
+    /// so popping visits them in ascending order.
+    fn successors(&self, v: T, subset: &HashSet<T>) -> Vec<T> {
+        let mut next = self.after.get(&v).map_or(vec![], |s| {
+            s.iter().filter(|w| subset.contains(w)).copied().collect()
+        });
+        next.sort_by(|a, b| b.cmp(a));
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_items_kept() {
+        let rules = Precedence::new([(47, 53), (97, 13), (97, 47), (47, 13), (53, 13)]);
+        let sorted = rules.topological_sort(&[53, 47, 47, 47, 13]).unwrap();
+        assert_eq!(sorted, [47, 47, 47, 53, 13]);
+        assert_eq!(sorted[sorted.len() / 2], 47);
+    }
+}
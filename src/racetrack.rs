@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+const NEIGHBORS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// A race track made of a single corridor from start to end.
+#[derive(Debug, Clone)]
+pub struct RaceTrack {
+    width: usize,
+    height: usize,
+    path: Vec<(i32, i32)>,
+    /// Picoseconds from the start for every track cell.
+    distance: Vec<Option<usize>>,
+}
+
+impl RaceTrack {
+    /// Follow the track from `start` to `end`. Returns `None` if the
+    /// track forks or doesn't reach `end`.
+    pub fn new(
+        width: usize,
+        height: usize,
+        is_track: impl Fn((i32, i32)) -> bool,
+        start: (i32, i32),
+        end: (i32, i32),
+    ) -> Option<Self> {
+        let mut track = Self {
+            width,
+            height,
+            path: vec![start],
+            distance: vec![None; width * height],
+        };
+        let idx = track.index(start)?;
+        track.distance[idx] = Some(0);
+
+        let mut pos = start;
+        while pos != end {
+            let next = NEIGHBORS
+                .iter()
+                .map(|(dr, dc)| (pos.0 + dr, pos.1 + dc))
+                .filter(|&p| {
+                    track
+                        .index(p)
+                        .is_some_and(|idx| is_track(p) && track.distance[idx].is_none())
+                })
+                .collect::<Vec<_>>();
+            let [next] = next[..] else {
+                return None;
+            };
+            pos = next;
+            let idx = track.index(pos)?;
+            track.distance[idx] = Some(track.path.len());
+            track.path.push(pos);
+        }
+
+        Some(track)
+    }
+
+    fn index(&self, (r, c): (i32, i32)) -> Option<usize> {
+        (r >= 0 && c >= 0 && (r as usize) < self.height && (c as usize) < self.width)
+            .then(|| r as usize * self.width + c as usize)
+    }
+
+    /// The cells of the track in race order, start and end included.
+    pub fn path(&self) -> &[(i32, i32)] {
+        &self.path
+    }
+
+    /// Picoseconds to finish without cheating.
+    pub fn time(&self) -> usize {
+        self.path.len() - 1
+    }
+
+    /// Number of cheats saving each amount of time, for cheats lasting
+    /// up to `radius` picoseconds. Only cheats that save time are
+    /// counted.
+    ///
+    /// Each track cell only looks at the diamond of cells within
+    /// `radius` of it, so the cost is linear in the track length.
+    pub fn cheat_savings(&self, radius: usize) -> BTreeMap<usize, usize> {
+        let radius = radius as i32;
+        let mut savings = BTreeMap::new();
+        for (from, &(r, c)) in self.path.iter().enumerate() {
+            for dr in -radius..=radius {
+                let reach = radius - dr.abs();
+                for dc in -reach..=reach {
+                    let Some(to) = self
+                        .index((r + dr, c + dc))
+                        .and_then(|idx| self.distance[idx])
+                    else {
+                        continue;
+                    };
+                    let cheat = (dr.abs() + dc.abs()) as usize;
+                    if to > from + cheat {
+                        *savings.entry(to - from - cheat).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+        savings
+    }
+
+    /// Number of cheats lasting up to `radius` picoseconds that save
+    /// at least `threshold`.
+    pub fn count_cheats(&self, radius: usize, threshold: usize) -> usize {
+        self.cheat_savings(radius)
+            .range(threshold..)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    fn example() -> RaceTrack {
+        let rows = EXAMPLE.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let find = |ch| {
+            rows.iter()
+                .enumerate()
+                .find_map(|(r, row)| {
+                    let c = row.iter().position(|&b| b == ch)?;
+                    Some((r as i32, c as i32))
+                })
+                .unwrap()
+        };
+        let is_track = |(r, c): (i32, i32)| rows[r as usize][c as usize] != b'#';
+        RaceTrack::new(rows[0].len(), rows.len(), is_track, find(b'S'), find(b'E')).unwrap()
+    }
+
+    #[test]
+    fn example_savings() {
+        let track = example();
+        assert_eq!(track.time(), 84);
+        assert_eq!(
+            track.cheat_savings(2),
+            BTreeMap::from([
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1),
+            ])
+        );
+
+        let long = track
+            .cheat_savings(20)
+            .into_iter()
+            .filter(|&(saved, _)| saved >= 50)
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            long,
+            BTreeMap::from([
+                (50, 32),
+                (52, 31),
+                (54, 29),
+                (56, 39),
+                (58, 25),
+                (60, 23),
+                (62, 20),
+                (64, 19),
+                (66, 12),
+                (68, 14),
+                (70, 12),
+                (72, 22),
+                (74, 4),
+                (76, 3),
+            ])
+        );
+        assert_eq!(track.count_cheats(20, 50), 285);
+    }
+}
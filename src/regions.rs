@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+const NEIGHBORS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// A 4-connected group of cells sharing the same label.
+#[derive(Debug, Clone)]
+pub struct Region<T> {
+    pub id: usize,
+    pub label: T,
+    /// `(row, col)` of every cell, in discovery order.
+    pub cells: Vec<(i32, i32)>,
+    pub perimeter: usize,
+    /// Number of straight fence sections, equal to the number of
+    /// corners.
+    pub sides: usize,
+    /// Top left and bottom right cells, inclusive.
+    pub bounding_box: ((i32, i32), (i32, i32)),
+    /// Number of separate pockets of other cells fully surrounded by
+    /// this region.
+    pub holes: usize,
+    /// Ids of regions lying entirely inside this region's holes.
+    pub enclosed: Vec<usize>,
+}
+
+impl<T> Region<T> {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+/// Splits a labelled grid into regions.
+#[derive(Debug, Clone)]
+pub struct RegionMap<T> {
+    width: usize,
+    height: usize,
+    /// Region id of every cell, row major.
+    ids: Vec<usize>,
+    regions: Vec<Region<T>>,
+}
+
+impl<T> RegionMap<T>
+where
+    T: Copy + Eq,
+{
+    /// `grid` is indexed `[row][col]` and every row must be the same
+    /// length.
+    pub fn new(grid: &[Vec<T>]) -> Self {
+        let height = grid.len();
+        let width = grid.first().map_or(0, |row| row.len());
+        assert!(grid.iter().all(|row| row.len() == width));
+
+        let mut map = Self {
+            width,
+            height,
+            ids: vec![usize::MAX; width * height],
+            regions: vec![],
+        };
+
+        for row in 0..height as i32 {
+            for col in 0..width as i32 {
+                if map.id_at((row, col)) == Some(usize::MAX) {
+                    map.flood(grid, (row, col));
+                }
+            }
+        }
+
+        for id in 0..map.regions.len() {
+            map.measure(id);
+        }
+        for id in 0..map.regions.len() {
+            map.find_holes(id);
+        }
+
+        map
+    }
+
+    pub fn regions(&self) -> &[Region<T>] {
+        &self.regions
+    }
+
+    pub fn region_at(&self, pos: (i32, i32)) -> Option<&Region<T>> {
+        self.id_at(pos).map(|id| &self.regions[id])
+    }
+
+    fn id_at(&self, (row, col): (i32, i32)) -> Option<usize> {
+        (row >= 0 && col >= 0 && (row as usize) < self.height && (col as usize) < self.width)
+            .then(|| self.ids[row as usize * self.width + col as usize])
+    }
+
+    fn flood(&mut self, grid: &[Vec<T>], start: (i32, i32)) {
+        let id = self.regions.len();
+        let label = grid[start.0 as usize][start.1 as usize];
+
+        let mut cells = vec![];
+        let mut stack = vec![start];
+        self.ids[start.0 as usize * self.width + start.1 as usize] = id;
+        while let Some(pos) = stack.pop() {
+            cells.push(pos);
+            for (dr, dc) in NEIGHBORS {
+                let next = (pos.0 + dr, pos.1 + dc);
+                if self.id_at(next) == Some(usize::MAX)
+                    && grid[next.0 as usize][next.1 as usize] == label
+                {
+                    self.ids[next.0 as usize * self.width + next.1 as usize] = id;
+                    stack.push(next);
+                }
+            }
+        }
+
+        self.regions.push(Region {
+            id,
+            label,
+            bounding_box: (start, start),
+            cells,
+            perimeter: 0,
+            sides: 0,
+            holes: 0,
+            enclosed: vec![],
+        });
+    }
+
+    fn measure(&mut self, id: usize) {
+        let inside = |pos: (i32, i32)| self.id_at(pos) == Some(id);
+
+        let region = &self.regions[id];
+        let mut perimeter = 0;
+        let mut corners = 0;
+        for &(r, c) in region.cells.iter() {
+            perimeter += NEIGHBORS
+                .iter()
+                .filter(|(dr, dc)| !inside((r + dr, c + dc)))
+                .count();
+
+            for (dr, dc) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+                let vertical = inside((r + dr, c));
+                let horizontal = inside((r, c + dc));
+                let diagonal = inside((r + dr, c + dc));
+                // outer corner, or inner corner of an L bend
+                if (!vertical && !horizontal) || (vertical && horizontal && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+
+        let rows = region.cells.iter().map(|p| p.0);
+        let cols = region.cells.iter().map(|p| p.1);
+        let bounding_box = (
+            (rows.clone().min().unwrap(), cols.clone().min().unwrap()),
+            (rows.max().unwrap(), cols.max().unwrap()),
+        );
+
+        let region = &mut self.regions[id];
+        region.perimeter = perimeter;
+        region.sides = corners;
+        region.bounding_box = bounding_box;
+    }
+
+    /// Flood the space around the region inside its bounding box grown
+    /// by one cell. Anything other than the region left unreached is
+    /// in a hole.
+    fn find_holes(&mut self, id: usize) {
+        let ((r0, c0), (r1, c1)) = self.regions[id].bounding_box;
+        let (r0, c0, r1, c1) = (r0 - 1, c0 - 1, r1 + 1, c1 + 1);
+        let in_box = |(r, c): (i32, i32)| r >= r0 && r <= r1 && c >= c0 && c <= c1;
+        let is_region = |pos: (i32, i32)| self.id_at(pos) == Some(id);
+
+        let mut outside = HashSet::from([(r0, c0)]);
+        let mut stack = vec![(r0, c0)];
+        while let Some((r, c)) = stack.pop() {
+            for (dr, dc) in NEIGHBORS {
+                let next = (r + dr, c + dc);
+                if in_box(next) && !is_region(next) && outside.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        let mut hole_cells = HashSet::new();
+        let mut holes = 0;
+        for r in r0..=r1 {
+            for c in c0..=c1 {
+                let start = (r, c);
+                if is_region(start) || outside.contains(&start) || hole_cells.contains(&start) {
+                    continue;
+                }
+                holes += 1;
+                hole_cells.insert(start);
+                let mut stack = vec![start];
+                while let Some((r, c)) = stack.pop() {
+                    for (dr, dc) in NEIGHBORS {
+                        let next = (r + dr, c + dc);
+                        if !is_region(next) && !outside.contains(&next) && hole_cells.insert(next) {
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+
+        let enclosed = hole_cells
+            .iter()
+            .filter_map(|&pos| self.id_at(pos))
+            .collect::<HashSet<_>>();
+        let mut enclosed = enclosed.into_iter().collect::<Vec<_>>();
+        enclosed.sort();
+
+        let region = &mut self.regions[id];
+        region.holes = holes;
+        region.enclosed = enclosed;
+    }
+}
+
+impl RegionMap<char> {
+    /// The grid with each region's cells drawn on its own ANSI 256
+    /// colour background.
+    pub fn render_colored(&self) -> String {
+        let mut s = String::new();
+        for row in 0..self.height as i32 {
+            for col in 0..self.width as i32 {
+                let region = self.region_at((row, col)).unwrap();
+                // step through the lighter part of the 6x6x6 colour cube
+                // so consecutive ids get visibly different colours
+                let n = (region.id * 23) % 64;
+                let colour = 16 + 36 * (2 + n / 16) + 6 * (2 + n / 4 % 4) + (2 + n % 4);
+                s.push_str(&format!("\x1b[30;48;5;{}m{}", colour, region.label));
+            }
+            s.push_str("\x1b[0m\n");
+        }
+        s
+    }
+}
@@ -0,0 +1,137 @@
+/// The allowed size of the change between adjacent levels.
+#[derive(Debug, Clone, Copy)]
+pub struct StepBounds {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Default for StepBounds {
+    fn default() -> Self {
+        Self { min: 1, max: 3 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+}
+
+/// How a report can be made safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Safety {
+    pub trend: Trend,
+    /// Indices of the levels to drop, in order. Empty if the report is
+    /// safe as it is.
+    pub removed: Vec<usize>,
+}
+
+/// Whether `levels` can be made safe by dropping at most
+/// `max_removals` of them: every step must go the same way and change
+/// by between `bounds.min` and `bounds.max`. Finds the fewest levels to
+/// drop, preferring an increasing report on a tie.
+///
+/// For each level and number dropped so far, this records whether a
+/// safe run can end at that level. A level can only follow one of the
+/// `max_removals + 1` before it, so the work is linear in the length of
+/// the report for a fixed number of removals.
+pub fn analyse(levels: &[i32], bounds: StepBounds, max_removals: usize) -> Option<Safety> {
+    [Trend::Increasing, Trend::Decreasing]
+        .into_iter()
+        .filter_map(|trend| analyse_trend(levels, bounds, max_removals, trend))
+        .min_by_key(|safety| safety.removed.len())
+}
+
+fn analyse_trend(
+    levels: &[i32],
+    bounds: StepBounds,
+    max_removals: usize,
+    trend: Trend,
+) -> Option<Safety> {
+    let n = levels.len();
+    if n == 0 {
+        return Some(Safety {
+            trend,
+            removed: vec![],
+        });
+    }
+
+    // in i64 so levels at the ends of the i32 range can't overflow
+    let step_ok = |a: i32, b: i32| {
+        let (a, b) = (a as i64, b as i64);
+        let diff = match trend {
+            Trend::Increasing => b - a,
+            Trend::Decreasing => a - b,
+        };
+        (bounds.min as i64..=bounds.max as i64).contains(&diff)
+    };
+
+    // reach[i][k]: a safe run can end at level i with k levels dropped
+    // before it, holding the previous kept level if there is one
+    let mut reach = vec![vec![None; max_removals + 1]; n];
+    for i in 0..n {
+        if i <= max_removals {
+            reach[i][i] = Some(None);
+        }
+        for j in i.saturating_sub(max_removals + 1)..i {
+            let gap = i - j - 1;
+            if !step_ok(levels[j], levels[i]) {
+                continue;
+            }
+            for k in gap..=max_removals {
+                if reach[i][k].is_none() && reach[j][k - gap].is_some() {
+                    reach[i][k] = Some(Some(j));
+                }
+            }
+        }
+    }
+
+    // the last kept level drops everything after it
+    let (mut last, mut dropped) = (0..n)
+        .rev()
+        .take(max_removals + 1)
+        .filter_map(|i| {
+            let after = n - 1 - i;
+            (0..=max_removals - after)
+                .find(|&k| reach[i][k].is_some())
+                .map(|k| (i, k + after))
+        })
+        .min_by_key(|&(_, total)| total)?;
+
+    let mut removed = ((last + 1)..n).collect::<Vec<_>>();
+    dropped -= removed.len();
+    loop {
+        match reach[last][dropped].unwrap() {
+            Some(prev) => {
+                removed.extend(prev + 1..last);
+                dropped -= last - prev - 1;
+                last = prev;
+            }
+            None => {
+                removed.extend(0..last);
+                break;
+            }
+        }
+    }
+    removed.sort();
+
+    Some(Safety { trend, removed })
+}
+
+/// Whether `levels` is safe without dropping anything.
+pub fn is_safe(levels: &[i32], bounds: StepBounds) -> bool {
+    analyse(levels, bounds, 0).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extreme_levels() {
+        let bounds = StepBounds::default();
+        assert_eq!(analyse(&[i32::MIN, i32::MAX], bounds, 0), None);
+        assert!(is_safe(&[i32::MAX - 2, i32::MAX], bounds));
+        assert!(is_safe(&[i32::MIN + 3, i32::MIN], bounds));
+    }
+}
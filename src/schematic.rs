@@ -0,0 +1,180 @@
+use std::{collections::HashMap, fmt::Display};
+
+/// Pin heights of a lock, measured down from the solid top row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lock {
+    pub heights: Vec<usize>,
+}
+
+/// Tooth heights of a key, measured up from the solid bottom row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub heights: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schematic {
+    Lock(Lock),
+    Key(Key),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchematicError {
+    /// The block isn't `width` by `height`.
+    Size { width: usize, height: usize },
+    /// Neither the top nor the bottom row is solid, or both are.
+    NotSolid,
+    /// A column has a gap, or filled cells away from the solid edge.
+    Column(usize),
+}
+
+impl Display for SchematicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchematicError::Size { width, height } => {
+                write!(f, "schematic is not {width} wide and {height} high")
+            }
+            SchematicError::NotSolid => {
+                write!(
+                    f,
+                    "schematic needs exactly one of the top or bottom rows solid"
+                )
+            }
+            SchematicError::Column(col) => write!(f, "column {col} is not a single run"),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+impl Schematic {
+    /// Read a block of `#` and `.` rows, which must be `width` columns
+    /// by `height` rows. A solid top row makes a lock and a solid
+    /// bottom row a key.
+    pub fn parse(rows: &[Vec<char>], width: usize, height: usize) -> Result<Self, SchematicError> {
+        if rows.len() != height || height < 2 || rows.iter().any(|row| row.len() != width) {
+            return Err(SchematicError::Size { width, height });
+        }
+
+        let solid = |row: &[char]| row.iter().all(|&ch| ch == '#');
+        let is_lock = match (solid(&rows[0]), solid(&rows[height - 1])) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => return Err(SchematicError::NotSolid),
+        };
+
+        let mut heights = Vec::with_capacity(width);
+        for col in 0..width {
+            // read from the solid edge inwards, the run must not restart
+            let mut cells = rows.iter().map(|row| row[col] == '#').collect::<Vec<_>>();
+            if !is_lock {
+                cells.reverse();
+            }
+            let filled = cells.iter().take_while(|&&f| f).count();
+            if cells[filled..].iter().any(|&f| f) {
+                return Err(SchematicError::Column(col));
+            }
+            heights.push(filled - 1);
+        }
+
+        Ok(if is_lock {
+            Schematic::Lock(Lock { heights })
+        } else {
+            Schematic::Key(Key { heights })
+        })
+    }
+}
+
+/// Keys indexed so locks can be checked against all of them at once.
+/// For each column and height there is a bitset of the keys no taller
+/// than that in the column, so the keys fitting a lock are the AND of
+/// one bitset per column. A lock and key of different widths never
+/// fit.
+#[derive(Debug, Clone)]
+pub struct KeyIndex {
+    /// `fits[col][h]`: keys with height at most `h` in `col`.
+    fits: Vec<Vec<Vec<u64>>>,
+    /// The keys of each width.
+    widths: HashMap<usize, Vec<u64>>,
+    space: usize,
+}
+
+impl KeyIndex {
+    /// `space` is the number of rows between the solid edges, so a
+    /// lock and key overlap if their heights in a column sum past it.
+    pub fn new(keys: &[Key], space: usize) -> Self {
+        let columns = keys.iter().map(|key| key.heights.len()).max().unwrap_or(0);
+        let words = keys.len().div_ceil(64);
+        let mut fits = vec![vec![vec![0u64; words]; space + 1]; columns];
+        let mut widths: HashMap<usize, Vec<u64>> = HashMap::new();
+        for (n, key) in keys.iter().enumerate() {
+            let bit = 1 << (n % 64);
+            widths
+                .entry(key.heights.len())
+                .or_insert_with(|| vec![0; words])[n / 64] |= bit;
+            for (col, &h) in key.heights.iter().enumerate() {
+                for bits in fits[col].iter_mut().skip(h) {
+                    bits[n / 64] |= bit;
+                }
+            }
+        }
+        Self {
+            fits,
+            widths,
+            space,
+        }
+    }
+
+    /// Number of keys that fit `lock` without overlapping.
+    pub fn count_fits(&self, lock: &Lock) -> usize {
+        let Some(mut bits) = self.widths.get(&lock.heights.len()).cloned() else {
+            return 0;
+        };
+        for (col, &h) in lock.heights.iter().enumerate() {
+            let Some(room) = self.space.checked_sub(h) else {
+                return 0;
+            };
+            bits.iter_mut()
+                .zip(self.fits[col][room].iter())
+                .for_each(|(a, b)| *a &= b);
+        }
+        bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+}
+
+/// Number of lock and key pairs that fit together.
+pub fn count_fits(locks: &[Lock], keys: &[Key], space: usize) -> usize {
+    let index = KeyIndex::new(keys, space);
+    locks.iter().map(|lock| index.count_fits(lock)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatched_widths_never_fit() {
+        let keys = [
+            Key {
+                heights: vec![0, 1, 2],
+            },
+            Key {
+                heights: vec![0, 1, 2, 3, 4],
+            },
+        ];
+        let narrow = Lock {
+            heights: vec![5, 4, 3],
+        };
+        let wide = Lock {
+            heights: vec![1, 1, 1, 1, 1],
+        };
+        let wider = Lock {
+            heights: vec![0; 7],
+        };
+        let index = KeyIndex::new(&keys, 5);
+        assert_eq!(index.count_fits(&narrow), 1);
+        assert_eq!(index.count_fits(&wide), 1);
+        assert_eq!(index.count_fits(&wider), 0);
+        assert_eq!(KeyIndex::new(&[], 5).count_fits(&wide), 0);
+    }
+}
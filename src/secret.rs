@@ -0,0 +1,141 @@
+use rayon::prelude::*;
+
+/// Secrets are pruned to this many bits after every mix.
+pub const SECRET_BITS: usize = 24;
+const MASK: u32 = (1 << SECRET_BITS) - 1;
+
+/// Number of distinct windows of four price changes, each change in
+/// `-9..=9`.
+pub const WINDOW_COUNT: usize = 19 * 19 * 19 * 19;
+
+/// One step of the monkey market generator.
+pub fn next(n: u32) -> u32 {
+    let n = (n ^ (n << 6)) & MASK;
+    let n = n ^ (n >> 5);
+    (n ^ (n << 11)) & MASK
+}
+
+/// Secret after `steps` applications of `next`.
+pub fn evolve(mut n: u32, steps: usize) -> u32 {
+    for _ in 0..steps {
+        n = next(n);
+    }
+    n
+}
+
+/// Evolve every seed in parallel.
+pub fn evolve_many(seeds: &[u32], steps: usize) -> Vec<u32> {
+    seeds.par_iter().map(|&n| evolve(n, steps)).collect()
+}
+
+/// `next` only shifts and xors, so it is a linear map on the 24 bit
+/// secret viewed as a vector over GF(2). The map is stored column by
+/// column: `cols[j]` is the image of bit `j`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Jump {
+    cols: [u32; SECRET_BITS],
+}
+
+impl Jump {
+    /// The map applying `next` `steps` times, built by repeated
+    /// squaring so large jumps stay cheap.
+    pub fn new(mut steps: u64) -> Self {
+        let mut result = Self::identity();
+        let mut base = Self {
+            cols: std::array::from_fn(|j| next(1 << j)),
+        };
+        while steps > 0 {
+            if steps & 1 == 1 {
+                result = base.then(&result);
+            }
+            base = base.then(&base);
+            steps >>= 1;
+        }
+        result
+    }
+
+    fn identity() -> Self {
+        Self {
+            cols: std::array::from_fn(|j| 1 << j),
+        }
+    }
+
+    pub fn apply(&self, n: u32) -> u32 {
+        (0..SECRET_BITS)
+            .filter(|j| n >> j & 1 == 1)
+            .fold(0, |acc, j| acc ^ self.cols[j])
+    }
+
+    /// The map applying `first` and then `self`.
+    fn then(&self, first: &Self) -> Self {
+        Self {
+            cols: first.cols.map(|c| self.apply(c)),
+        }
+    }
+}
+
+/// Dense index of a window of four price changes.
+pub fn window_index(changes: [i8; 4]) -> usize {
+    changes
+        .iter()
+        .fold(0, |acc, &d| acc * 19 + (d + 9) as usize)
+}
+
+pub fn decode_window(mut index: usize) -> [i8; 4] {
+    let mut changes = [0; 4];
+    for d in changes.iter_mut().rev() {
+        *d = (index % 19) as i8 - 9;
+        index /= 19;
+    }
+    changes
+}
+
+/// Total bananas bought for every window of four price changes. Each
+/// buyer sells at the first time a window appears among its `steps`
+/// generated prices. Buyers are split across threads, each with its
+/// own flat accumulator, and the accumulators are summed at the end.
+pub fn window_totals(seeds: &[u32], steps: usize) -> Vec<u32> {
+    seeds
+        .par_iter()
+        .enumerate()
+        .fold(
+            || (vec![0u32; WINDOW_COUNT], vec![usize::MAX; WINDOW_COUNT]),
+            |(mut totals, mut seen_by), (buyer, &seed)| {
+                let mut secret = seed;
+                let mut price = (secret % 10) as i8;
+                let mut window = 0;
+                for step in 0..steps {
+                    secret = next(secret);
+                    let new_price = (secret % 10) as i8;
+                    window = (window * 19 + (new_price - price + 9) as usize) % WINDOW_COUNT;
+                    price = new_price;
+
+                    if step >= 3 && seen_by[window] != buyer {
+                        seen_by[window] = buyer;
+                        totals[window] += price as u32;
+                    }
+                }
+                (totals, seen_by)
+            },
+        )
+        .map(|(totals, _)| totals)
+        .reduce(
+            || vec![0; WINDOW_COUNT],
+            |mut a, b| {
+                a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                a
+            },
+        )
+}
+
+/// The window of price changes selling for the most bananas and the
+/// number of bananas it gets.
+pub fn best_window(seeds: &[u32], steps: usize) -> ([i8; 4], u32) {
+    let totals = window_totals(seeds, steps);
+    let (index, &best) = totals
+        .iter()
+        .enumerate()
+        .max_by_key(|&(idx, total)| (total, std::cmp::Reverse(idx)))
+        .unwrap();
+    (decode_window(index), best)
+}
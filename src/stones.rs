@@ -0,0 +1,190 @@
+use std::{collections::HashMap, fmt::Display, ops::AddAssign};
+
+use num::Zero;
+
+/// A rule would turn `stone` into a number too big for a `u64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub stone: u64,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stone {} overflowed u64", self.stone)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// One rule for what a stone turns into when blinked at.
+pub trait Rule: Send + Sync {
+    /// The stones `stone` becomes, or `None` if the rule doesn't apply.
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow>;
+}
+
+/// `0` becomes `1`.
+pub struct ZeroToOne;
+
+impl Rule for ZeroToOne {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        Ok((stone == 0).then(|| vec![1]))
+    }
+}
+
+/// A stone with an even number of digits splits into its left and
+/// right halves.
+pub struct SplitEvenDigits;
+
+impl Rule for SplitEvenDigits {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+        if !digits.is_multiple_of(2) {
+            return Ok(None);
+        }
+        let half = 10u64.pow(digits / 2);
+        Ok(Some(vec![stone / half, stone % half]))
+    }
+}
+
+/// Any stone is multiplied by a constant.
+pub struct Multiply(pub u64);
+
+impl Rule for Multiply {
+    fn apply(&self, stone: u64) -> Result<Option<Vec<u64>>, Overflow> {
+        let product = stone.checked_mul(self.0).ok_or(Overflow { stone })?;
+        Ok(Some(vec![product]))
+    }
+}
+
+/// Rules tried in order, the first that applies wins. A stone no rule
+/// applies to is left as it is.
+pub struct RuleSet {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleSet {
+    pub fn new(rules: Vec<Box<dyn Rule>>) -> Self {
+        Self { rules }
+    }
+
+    /// The rules from the puzzle: `0` to `1`, split even digits, else
+    /// multiply by 2024.
+    pub fn puzzle() -> Self {
+        Self::new(vec![
+            Box::new(ZeroToOne),
+            Box::new(SplitEvenDigits),
+            Box::new(Multiply(2024)),
+        ])
+    }
+
+    pub fn apply(&self, stone: u64) -> Result<Vec<u64>, Overflow> {
+        for rule in self.rules.iter() {
+            if let Some(stones) = rule.apply(stone)? {
+                return Ok(stones);
+            }
+        }
+        Ok(vec![stone])
+    }
+}
+
+/// A line of stones kept as how many there are of each number, since
+/// their order never matters. `C` is the count type; use `u128` or
+/// `BigUint` when running for a very long time.
+#[derive(Debug, Clone)]
+pub struct Stones<C> {
+    counts: HashMap<u64, C>,
+    /// What each number seen so far turns into. The numbers soon
+    /// settle into a closed set, after which every blink is only
+    /// lookups shared by all stones with the same number.
+    transitions: HashMap<u64, Vec<u64>>,
+    blinks: usize,
+}
+
+impl<C> Stones<C>
+where
+    C: Clone + Zero + From<u8> + for<'a> AddAssign<&'a C>,
+{
+    pub fn new(stones: &[u64]) -> Self {
+        let mut counts: HashMap<u64, C> = HashMap::new();
+        for &stone in stones {
+            *counts.entry(stone).or_insert_with(C::zero) += &C::from(1);
+        }
+        Self {
+            counts,
+            transitions: HashMap::new(),
+            blinks: 0,
+        }
+    }
+
+    /// Blink once. On overflow the stones are left as they were.
+    pub fn blink(&mut self, rules: &RuleSet) -> Result<(), Overflow> {
+        let mut next: HashMap<u64, C> = HashMap::with_capacity(self.counts.len());
+        for (stone, count) in self.counts.iter() {
+            let into = match self.transitions.get(stone) {
+                Some(into) => into,
+                None => self
+                    .transitions
+                    .entry(*stone)
+                    .or_insert(rules.apply(*stone)?),
+            };
+            for new in into.iter() {
+                *next.entry(*new).or_insert_with(C::zero) += count;
+            }
+        }
+        self.counts = next;
+        self.blinks += 1;
+        Ok(())
+    }
+
+    /// Blink `n` times, returning the number of distinct stone numbers
+    /// after each blink.
+    pub fn blink_many(&mut self, rules: &RuleSet, n: usize) -> Result<Vec<usize>, Overflow> {
+        (0..n)
+            .map(|_| {
+                self.blink(rules)?;
+                Ok(self.distinct())
+            })
+            .collect()
+    }
+
+    pub fn blinks(&self) -> usize {
+        self.blinks
+    }
+
+    /// Number of different numbers on the stones.
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Total number of stones.
+    pub fn total(&self) -> C {
+        let mut total = C::zero();
+        for count in self.counts.values() {
+            total += count;
+        }
+        total
+    }
+
+    pub fn counts(&self) -> &HashMap<u64, C> {
+        &self.counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_is_an_error() {
+        let rules = RuleSet::puzzle();
+        let mut stones = Stones::<u128>::new(&[12345678901234567]);
+        assert_eq!(
+            stones.blink(&rules),
+            Err(Overflow {
+                stone: 12345678901234567
+            })
+        );
+        assert_eq!(stones.blinks(), 0);
+        assert_eq!(stones.total(), 1);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+
+use num::Integer;
+
+/// A point moving at a constant velocity on a wrapping grid.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Particle {
+    pub pos: (i64, i64),
+    pub vel: (i64, i64),
+}
+
+/// How tightly a frame of particles is packed together. For every
+/// metric a lower score means a more ordered frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClusterMetric {
+    /// Sum of the variances of the x and y coordinates.
+    Variance,
+    /// Negated size of the largest 4-connected group of occupied cells.
+    LargestComponent,
+    /// Shannon entropy (bits) of the particle counts in square blocks
+    /// of the given size. A size below 1 is taken as 1.
+    Entropy { block: i64 },
+}
+
+/// A `width` x `height` grid whose edges wrap around.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Torus {
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Torus {
+    pub fn new(width: i64, height: i64) -> Self {
+        Self { width, height }
+    }
+
+    /// Position of the particle after `t` steps. `t` may be negative.
+    pub fn position_at(&self, p: &Particle, t: i64) -> (i64, i64) {
+        (
+            (p.pos.0 + p.vel.0 * t).mod_floor(&self.width),
+            (p.pos.1 + p.vel.1 * t).mod_floor(&self.height),
+        )
+    }
+
+    pub fn positions_at(&self, particles: &[Particle], t: i64) -> Vec<(i64, i64)> {
+        particles.iter().map(|p| self.position_at(p, t)).collect()
+    }
+
+    /// Number of steps before the particle first returns to its start.
+    pub fn particle_period(&self, p: &Particle) -> i64 {
+        let px = self.width / p.vel.0.gcd(&self.width);
+        let py = self.height / p.vel.1.gcd(&self.height);
+        px.lcm(&py)
+    }
+
+    /// Number of steps before every particle is back at its start at
+    /// the same time.
+    pub fn period(&self, particles: &[Particle]) -> i64 {
+        particles
+            .iter()
+            .fold(1, |acc, p| acc.lcm(&self.particle_period(p)))
+    }
+
+    pub fn score(&self, positions: &[(i64, i64)], metric: ClusterMetric) -> f64 {
+        match metric {
+            ClusterMetric::Variance => {
+                variance(positions.iter().map(|p| p.0)) + variance(positions.iter().map(|p| p.1))
+            }
+            ClusterMetric::LargestComponent => -(largest_component(positions) as f64),
+            ClusterMetric::Entropy { block } => {
+                let block = block.max(1);
+                let counts = positions.iter().map(|p| (p.0 / block, p.1 / block)).fold(
+                    HashMap::new(),
+                    |mut acc, cell| {
+                        *acc.entry(cell).or_insert(0) += 1;
+                        acc
+                    },
+                );
+                let total = positions.len() as f64;
+                counts
+                    .values()
+                    .map(|&n| {
+                        let p = n as f64 / total;
+                        -p * p.log2()
+                    })
+                    .sum()
+            }
+        }
+    }
+
+    /// Score every step in `times`, most ordered frame first.
+    pub fn rank_frames(
+        &self,
+        particles: &[Particle],
+        times: impl Iterator<Item = i64>,
+        metric: ClusterMetric,
+    ) -> Vec<(i64, f64)> {
+        let mut scores = times
+            .map(|t| (t, self.score(&self.positions_at(particles, t), metric)))
+            .collect::<Vec<_>>();
+        scores.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        scores
+    }
+
+    /// Find the step at which the particles are most tightly packed.
+    /// The x coordinates repeat every `width` steps and the y
+    /// coordinates every `height` steps, so each axis is minimised
+    /// separately by variance and the two offsets are combined with
+    /// the chinese remainder theorem.
+    pub fn most_clustered_time(&self, particles: &[Particle]) -> Option<i64> {
+        let best_offset = |modulus: i64, axis: fn(&(i64, i64)) -> i64| {
+            (0..modulus)
+                .map(|t| {
+                    let positions = self.positions_at(particles, t);
+                    (variance(positions.iter().map(axis)), t)
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, t)| t)
+        };
+
+        let tx = best_offset(self.width, |p| p.0)?;
+        let ty = best_offset(self.height, |p| p.1)?;
+        crt(&[(tx, self.width), (ty, self.height)]).map(|(t, _)| t)
+    }
+}
+
+/// Solve `x = r (mod m)` for every `(r, m)` pair. Moduli need not be
+/// coprime. Returns the smallest non-negative solution along with the
+/// combined modulus, or `None` if the congruences conflict.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(r1, m1): (i64, i64), &(r2, m2)| {
+            let egcd = m1.extended_gcd(&m2);
+            let g = egcd.gcd;
+            if (r2 - r1) % g != 0 {
+                return None;
+            }
+            let lcm = m1 / g * m2;
+            let k = ((r2 - r1) / g) as i128 * egcd.x as i128 % (m2 / g) as i128;
+            let r = (r1 as i128 + m1 as i128 * k).rem_euclid(lcm as i128);
+            Some((r as i64, lcm))
+        })
+}
+
+fn variance(values: impl Iterator<Item = i64>) -> f64 {
+    let (n, sum, sum_sq) = values.fold((0.0, 0.0, 0.0), |(n, s, sq), v| {
+        let v = v as f64;
+        (n + 1.0, s + v, sq + v * v)
+    });
+    if n == 0.0 {
+        return 0.0;
+    }
+    let mean = sum / n;
+    sum_sq / n - mean * mean
+}
+
+fn largest_component(positions: &[(i64, i64)]) -> usize {
+    let mut unvisited: HashSet<(i64, i64)> = positions.iter().copied().collect();
+    let mut largest = 0;
+
+    while let Some(&start) = unvisited.iter().next() {
+        unvisited.remove(&start);
+        let mut stack = vec![start];
+        let mut size = 0;
+        while let Some((x, y)) = stack.pop() {
+            size += 1;
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if unvisited.remove(&next) {
+                    stack.push(next);
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+
+    largest
+}
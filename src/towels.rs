@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: HashMap<u8, usize>,
+    /// Index of the pattern ending at this node.
+    pattern: Option<usize>,
+}
+
+/// A set of patterns stored in a trie, used to split designs into a
+/// sequence of patterns.
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    nodes: Vec<TrieNode>,
+    patterns: Vec<String>,
+}
+
+impl PatternSet {
+    pub fn new<S: AsRef<str>>(patterns: impl IntoIterator<Item = S>) -> Self {
+        let mut set = Self {
+            nodes: vec![TrieNode::default()],
+            patterns: vec![],
+        };
+        for pattern in patterns {
+            set.insert(pattern.as_ref());
+        }
+        set
+    }
+
+    fn insert(&mut self, pattern: &str) {
+        let mut node = 0;
+        for &b in pattern.as_bytes() {
+            node = match self.nodes[node].children.get(&b) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node].children.insert(b, next);
+                    next
+                }
+            };
+        }
+        if self.nodes[node].pattern.is_none() {
+            self.nodes[node].pattern = Some(self.patterns.len());
+            self.patterns.push(pattern.to_string());
+        }
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Every pattern that matches `design` starting at `pos`, as
+    /// `(pattern index, end position)` from shortest to longest.
+    fn matches_at<'a>(
+        &'a self,
+        design: &'a [u8],
+        pos: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let mut node = Some(0);
+        design[pos..]
+            .iter()
+            .enumerate()
+            .map_while(move |(n, b)| {
+                node = self.nodes[node?].children.get(b).copied();
+                Some((node?, pos + n + 1))
+            })
+            .filter_map(|(node, end)| self.nodes[node].pattern.map(|p| (p, end)))
+    }
+
+    /// Number of ways to split the suffix of `design` starting at each
+    /// position into patterns, filled in from the end.
+    fn suffix_counts(&self, design: &[u8]) -> Vec<u128> {
+        let mut ways = vec![0u128; design.len() + 1];
+        ways[design.len()] = 1;
+        for pos in (0..design.len()).rev() {
+            ways[pos] = self
+                .matches_at(design, pos)
+                .fold(0u128, |acc, (_, end)| acc.saturating_add(ways[end]));
+        }
+        ways
+    }
+
+    pub fn is_possible(&self, design: &str) -> bool {
+        self.count_decompositions(design) > 0
+    }
+
+    /// Number of distinct ways to build `design` out of patterns,
+    /// saturating at `u128::MAX`.
+    pub fn count_decompositions(&self, design: &str) -> u128 {
+        self.suffix_counts(design.as_bytes())[0]
+    }
+
+    /// One way of building `design`, taking the shortest usable pattern
+    /// at every step.
+    pub fn decomposition(&self, design: &str) -> Option<Vec<&str>> {
+        let design = design.as_bytes();
+        let ways = self.suffix_counts(design);
+        if ways[0] == 0 {
+            return None;
+        }
+
+        let mut pos = 0;
+        let mut parts = vec![];
+        while pos < design.len() {
+            let (pattern, end) = self
+                .matches_at(design, pos)
+                .find(|&(_, end)| ways[end] > 0)
+                .unwrap();
+            parts.push(self.patterns[pattern].as_str());
+            pos = end;
+        }
+        Some(parts)
+    }
+
+    /// Up to `limit` ways of building `design`. Only positions that
+    /// can still reach the end are followed, so no work is wasted on
+    /// dead ends.
+    pub fn decompositions(&self, design: &str, limit: usize) -> Vec<Vec<&str>> {
+        let bytes = design.as_bytes();
+        let ways = self.suffix_counts(bytes);
+        let mut found = vec![];
+        self.decompositions_aux(bytes, &ways, 0, &mut vec![], &mut found, limit);
+        found
+    }
+
+    fn decompositions_aux<'a>(
+        &'a self,
+        design: &[u8],
+        ways: &[u128],
+        pos: usize,
+        parts: &mut Vec<&'a str>,
+        found: &mut Vec<Vec<&'a str>>,
+        limit: usize,
+    ) {
+        if found.len() >= limit || ways[pos] == 0 {
+            return;
+        }
+        if pos == design.len() {
+            found.push(parts.clone());
+            return;
+        }
+        for (pattern, end) in self.matches_at(design, pos) {
+            parts.push(&self.patterns[pattern]);
+            self.decompositions_aux(design, ways, end, parts, found, limit);
+            parts.pop();
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+const NEIGHBORS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// What can be reached from one source cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceReach {
+    pub source: (i32, i32),
+    /// Sink cells reachable from the source, in row major order.
+    pub sinks: Vec<(i32, i32)>,
+    /// Number of distinct paths from the source ending at any sink.
+    pub paths: u64,
+}
+
+/// Count trails on a grid where a step to an orthogonal neighbour is
+/// allowed when `can_step(from, to)` holds. The steps must form a DAG
+/// (e.g. strictly increasing values), which is checked; `None` is
+/// returned if they contain a cycle.
+///
+/// Every cell is visited once in reverse topological order, carrying a
+/// bitset of reachable sinks and a path count, so the cost doesn't
+/// depend on how many sources share the same trails.
+pub fn analyse<T: Copy>(
+    grid: &[Vec<T>],
+    can_step: impl Fn(T, T) -> bool,
+    is_source: impl Fn(T) -> bool,
+    is_sink: impl Fn(T) -> bool,
+) -> Option<Vec<SourceReach>> {
+    let height = grid.len();
+    let width = grid.first().map_or(0, |row| row.len());
+    let index = |(r, c): (i32, i32)| r as usize * width + c as usize;
+    let pos_of = |idx: usize| ((idx / width) as i32, (idx % width) as i32);
+    let value = |(r, c): (i32, i32)| grid[r as usize][c as usize];
+
+    let successors = |pos: (i32, i32)| {
+        NEIGHBORS
+            .iter()
+            .map(|(dr, dc)| (pos.0 + dr, pos.1 + dc))
+            .filter(|&(r, c)| r >= 0 && c >= 0 && (r as usize) < height && (c as usize) < width)
+            .filter(|&next| can_step(value(pos), value(next)))
+            .collect::<Vec<_>>()
+    };
+
+    // Kahn's algorithm for a topological order of the cells
+    let cells = width * height;
+    let mut in_degree = vec![0usize; cells];
+    for idx in 0..cells {
+        for next in successors(pos_of(idx)) {
+            in_degree[index(next)] += 1;
+        }
+    }
+    let mut queue = (0..cells)
+        .filter(|&idx| in_degree[idx] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(cells);
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        for next in successors(pos_of(idx)) {
+            let next = index(next);
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+    if order.len() != cells {
+        return None;
+    }
+
+    let sink_ids = (0..cells)
+        .filter(|&idx| is_sink(value(pos_of(idx))))
+        .collect::<Vec<_>>();
+    let mut sink_bit = vec![None; cells];
+    for (bit, &idx) in sink_ids.iter().enumerate() {
+        sink_bit[idx] = Some(bit);
+    }
+
+    let words = sink_ids.len().div_ceil(64);
+    let mut reach = vec![vec![0u64; words]; cells];
+    let mut paths = vec![0u64; cells];
+
+    for &idx in order.iter().rev() {
+        let pos = pos_of(idx);
+        let mut bits = vec![0u64; words];
+        let mut count = 0;
+        if let Some(bit) = sink_bit[idx] {
+            bits[bit / 64] |= 1 << (bit % 64);
+            count += 1;
+        }
+        for next in successors(pos) {
+            let next = index(next);
+            bits.iter_mut()
+                .zip(reach[next].iter())
+                .for_each(|(a, b)| *a |= b);
+            count += paths[next];
+        }
+        reach[idx] = bits;
+        paths[idx] = count;
+    }
+
+    let results = (0..cells)
+        .filter(|&idx| is_source(value(pos_of(idx))))
+        .map(|idx| SourceReach {
+            source: pos_of(idx),
+            sinks: sink_ids
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| reach[idx][bit / 64] >> (bit % 64) & 1 == 1)
+                .map(|(_, &sink)| pos_of(sink))
+                .collect(),
+            paths: paths[idx],
+        })
+        .collect();
+
+    Some(results)
+}
@@ -0,0 +1,433 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Write},
+    io,
+    thread::sleep,
+    time::Duration,
+};
+
+use clap::Parser;
+
+use crate::Args;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    N,
+    S,
+    E,
+    W,
+}
+
+impl Direction {
+    pub fn delta(self) -> (i32, i32) {
+        use Direction::*;
+        match self {
+            N => (-1, 0),
+            S => (1, 0),
+            E => (0, 1),
+            W => (0, -1),
+        }
+    }
+
+    fn step(self, (r, c): (i32, i32)) -> (i32, i32) {
+        let (dr, dc) = self.delta();
+        (r + dr, c + dc)
+    }
+}
+
+pub type ObjectId = usize;
+
+/// A map tile that isn't one of `#`, `.`, `O` or `@`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileError {
+    pub tile: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Display for TileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown tile {:?} at row {} column {}",
+            self.tile, self.row, self.col
+        )
+    }
+}
+
+impl std::error::Error for TileError {}
+
+/// A warehouse of walls and rigid boxes with a robot pushing them
+/// around. A box can be any shape; it is just the set of cells it
+/// covers, and it always moves as one piece.
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    width: usize,
+    height: usize,
+    walls: HashSet<(i32, i32)>,
+    objects: Vec<Vec<(i32, i32)>>,
+    occupant: HashMap<(i32, i32), ObjectId>,
+    robot: (i32, i32),
+}
+
+impl Warehouse {
+    pub fn new(
+        width: usize,
+        height: usize,
+        walls: impl IntoIterator<Item = (i32, i32)>,
+        robot: (i32, i32),
+    ) -> Self {
+        Self {
+            width,
+            height,
+            walls: walls.into_iter().collect(),
+            objects: vec![],
+            occupant: HashMap::new(),
+            robot,
+        }
+    }
+
+    /// Build from map rows of `#`, `.`, `O` and `@`, stretching every
+    /// cell `scale` times horizontally. Boxes become one object
+    /// `scale` cells wide and the robot stays on the left cell.
+    pub fn from_rows(rows: &[Vec<char>], scale: usize) -> Result<Self, TileError> {
+        let scale = scale.max(1);
+        let mut warehouse = Self::new(
+            rows.first().map_or(0, |row| row.len()) * scale,
+            rows.len(),
+            [],
+            (0, 0),
+        );
+        for (r, row) in rows.iter().enumerate() {
+            for (c, ch) in row.iter().enumerate() {
+                let cells = (0..scale)
+                    .map(|n| (r as i32, (c * scale + n) as i32))
+                    .collect::<Vec<_>>();
+                match ch {
+                    '#' => warehouse.walls.extend(cells),
+                    'O' => {
+                        warehouse.add_object(cells);
+                    }
+                    '@' => warehouse.robot = cells[0],
+                    '.' => (),
+                    &tile => {
+                        return Err(TileError {
+                            tile,
+                            row: r,
+                            col: c,
+                        })
+                    }
+                }
+            }
+        }
+        Ok(warehouse)
+    }
+
+    /// Place a box covering `cells`. Returns `None`, leaving the
+    /// warehouse unchanged, if any of them is already taken.
+    pub fn add_object(&mut self, cells: Vec<(i32, i32)>) -> Option<ObjectId> {
+        if cells.iter().any(|pos| !self.is_free(*pos)) {
+            return None;
+        }
+        let id = self.objects.len();
+        for pos in cells.iter() {
+            self.occupant.insert(*pos, id);
+        }
+        self.objects.push(cells);
+        Some(id)
+    }
+
+    fn is_free(&self, pos: (i32, i32)) -> bool {
+        !self.walls.contains(&pos) && !self.occupant.contains_key(&pos) && pos != self.robot
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn robot(&self) -> (i32, i32) {
+        self.robot
+    }
+
+    pub fn is_wall(&self, pos: (i32, i32)) -> bool {
+        self.walls.contains(&pos)
+    }
+
+    pub fn object_at(&self, pos: (i32, i32)) -> Option<ObjectId> {
+        self.occupant.get(&pos).copied()
+    }
+
+    /// The cells covered by each box, indexed by id.
+    pub fn objects(&self) -> &[Vec<(i32, i32)>] {
+        &self.objects
+    }
+
+    /// Every box the robot would push moving in `direction`, or `None`
+    /// if any of them (or the robot) would hit a wall.
+    pub fn pushed_by(&self, direction: Direction) -> Option<Vec<ObjectId>> {
+        let mut touched = vec![];
+        let mut seen = HashSet::new();
+        let mut pending = vec![direction.step(self.robot)];
+        while let Some(pos) = pending.pop() {
+            if self.walls.contains(&pos) {
+                return None;
+            }
+            let Some(id) = self.object_at(pos) else {
+                continue;
+            };
+            if seen.insert(id) {
+                touched.push(id);
+                pending.extend(self.objects[id].iter().map(|&p| direction.step(p)));
+            }
+        }
+        Some(touched)
+    }
+
+    /// Move the robot, pushing everything in its way. The boxes move
+    /// together or not at all. Returns the boxes that moved, or `None`
+    /// if the robot is blocked.
+    pub fn try_move(&mut self, direction: Direction) -> Option<Vec<ObjectId>> {
+        let touched = self.pushed_by(direction)?;
+        for &id in touched.iter() {
+            for pos in self.objects[id].iter() {
+                self.occupant.remove(pos);
+            }
+        }
+        for &id in touched.iter() {
+            for pos in self.objects[id].iter_mut() {
+                *pos = direction.step(*pos);
+                self.occupant.insert(*pos, id);
+            }
+        }
+        self.robot = direction.step(self.robot);
+        Some(touched)
+    }
+
+    /// 100 times the distance from the top edge plus the distance from
+    /// the left edge, measured to the box's nearest cells.
+    pub fn gps(&self, id: ObjectId) -> i64 {
+        let cells = &self.objects[id];
+        let top = cells.iter().map(|&(r, _)| r).min().unwrap_or(0);
+        let left = cells.iter().map(|&(_, c)| c).min().unwrap_or(0);
+        100 * top as i64 + left as i64
+    }
+
+    pub fn gps_sum(&self) -> i64 {
+        (0..self.objects.len()).map(|id| self.gps(id)).sum()
+    }
+
+    /// The character drawn for `pos`: single cell boxes are `O` and
+    /// wider ones `[`, `=`, `]` along each row.
+    pub fn tile_char(&self, pos: (i32, i32)) -> char {
+        if pos == self.robot {
+            return '@';
+        }
+        if self.walls.contains(&pos) {
+            return '#';
+        }
+        let Some(id) = self.object_at(pos) else {
+            return '.';
+        };
+        let same = |p| self.object_at(p) == Some(id);
+        match (same(Direction::W.step(pos)), same(Direction::E.step(pos))) {
+            (false, false) => 'O',
+            (false, true) => '[',
+            (true, true) => '=',
+            (true, false) => ']',
+        }
+    }
+
+    /// Like `Display`, but with the boxes in `highlight` picked out in
+    /// ANSI colour and the robot in bold.
+    pub fn render_highlighted(&self, highlight: &[ObjectId]) -> String {
+        let mut s = String::new();
+        for r in 0..self.height as i32 {
+            for c in 0..self.width as i32 {
+                let ch = self.tile_char((r, c));
+                let moved = self
+                    .object_at((r, c))
+                    .is_some_and(|id| highlight.contains(&id));
+                if moved {
+                    s.push_str(&format!("\x1b[30;43m{ch}\x1b[0m"));
+                } else if (r, c) == self.robot {
+                    s.push_str(&format!("\x1b[1;31m{ch}\x1b[0m"));
+                } else {
+                    s.push(ch);
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl Display for Warehouse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for r in 0..self.height as i32 {
+            for c in 0..self.width as i32 {
+                f.write_char(self.tile_char((r, c)))?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// One move of the robot as it was recorded.
+#[derive(Debug, Clone)]
+pub struct ReplayStep {
+    pub direction: Direction,
+    /// The boxes pushed, or `None` if the robot was blocked.
+    pub moved: Option<Vec<ObjectId>>,
+}
+
+/// Every move of a run through the warehouse, so any point of it can
+/// be looked at again without rerunning from the start.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    steps: Vec<ReplayStep>,
+    /// The state before every `CHECKPOINT_EVERY`th move.
+    checkpoints: Vec<Warehouse>,
+}
+
+impl Replay {
+    const CHECKPOINT_EVERY: usize = 256;
+
+    /// Run `directions` from `warehouse`, recording each move.
+    pub fn record(mut warehouse: Warehouse, directions: &[Direction]) -> Self {
+        let mut steps = Vec::with_capacity(directions.len());
+        let mut checkpoints = vec![];
+        for (n, &direction) in directions.iter().enumerate() {
+            if n % Self::CHECKPOINT_EVERY == 0 {
+                checkpoints.push(warehouse.clone());
+            }
+            let moved = warehouse.try_move(direction);
+            steps.push(ReplayStep { direction, moved });
+        }
+        if directions.is_empty() {
+            checkpoints.push(warehouse);
+        }
+        Self { steps, checkpoints }
+    }
+
+    pub fn steps(&self) -> &[ReplayStep] {
+        &self.steps
+    }
+
+    /// Number of moves recorded.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The warehouse after the first `n` moves, starting from the
+    /// nearest checkpoint. `n` is clamped to the number of moves.
+    pub fn state_at(&self, n: usize) -> Warehouse {
+        let n = n.min(self.len());
+        let checkpoint = (n / Self::CHECKPOINT_EVERY).min(self.checkpoints.len() - 1);
+        let mut warehouse = self.checkpoints[checkpoint].clone();
+        for step in &self.steps[checkpoint * Self::CHECKPOINT_EVERY..n] {
+            warehouse.try_move(step.direction);
+        }
+        warehouse
+    }
+
+    /// The warehouse after move `n` (counting from 1) with the boxes
+    /// that move pushed highlighted. Move 0 is the starting state.
+    pub fn render_step(&self, n: usize) -> String {
+        let n = n.min(self.len());
+        let moved = match n {
+            0 => &[][..],
+            n => self.steps[n - 1].moved.as_deref().unwrap_or(&[]),
+        };
+        self.state_at(n).render_highlighted(moved)
+    }
+
+    /// Play moves `from..=to` back as a terminal animation, redrawing
+    /// in place every `delay`.
+    pub fn play(
+        &self,
+        out: &mut impl io::Write,
+        from: usize,
+        to: usize,
+        delay: Duration,
+    ) -> io::Result<()> {
+        let to = to.min(self.len());
+        let from = from.min(to);
+        let mut warehouse = self.state_at(from);
+        for n in from..=to {
+            let moved = match n {
+                0 => &[][..],
+                n if n == from => self.steps[n - 1].moved.as_deref().unwrap_or(&[]),
+                n => {
+                    let step = &self.steps[n - 1];
+                    warehouse.try_move(step.direction);
+                    step.moved.as_deref().unwrap_or(&[])
+                }
+            };
+            let direction = match n {
+                0 => "start".to_string(),
+                n => format!("{:?}", self.steps[n - 1].direction),
+            };
+            write!(out, "\x1b[H\x1b[2J")?;
+            writeln!(out, "move {n}/{} {direction}", self.len())?;
+            write!(out, "{}", warehouse.render_highlighted(moved))?;
+            out.flush()?;
+            sleep(delay);
+        }
+        Ok(())
+    }
+}
+
+/// Command line for the warehouse examples: the input file plus
+/// options to look at the moves as they are replayed.
+#[derive(Parser, Debug, Clone)]
+pub struct ReplayArgs {
+    #[command(flatten)]
+    pub args: Args,
+
+    /// Print the warehouse after this many moves, with the boxes the
+    /// last move pushed highlighted
+    #[arg(long)]
+    pub show: Option<usize>,
+
+    /// Animate the moves in the terminal
+    #[arg(long)]
+    pub play: bool,
+
+    /// First move to animate
+    #[arg(long, default_value_t = 0)]
+    pub from: usize,
+
+    /// Last move to animate, defaults to the end
+    #[arg(long)]
+    pub to: Option<usize>,
+
+    /// Milliseconds between animation frames
+    #[arg(long, default_value_t = 100)]
+    pub delay: u64,
+}
+
+impl ReplayArgs {
+    /// Make every move in `directions`, showing or animating them to
+    /// stdout as asked, and return the warehouse at the end.
+    pub fn replay(&self, warehouse: Warehouse, directions: &[Direction]) -> io::Result<Warehouse> {
+        let replay = Replay::record(warehouse, directions);
+        if let Some(n) = self.show {
+            print!("{}", replay.render_step(n));
+        }
+        if self.play {
+            let to = self.to.unwrap_or(replay.len());
+            let delay = Duration::from_millis(self.delay);
+            replay.play(&mut io::stdout(), self.from, to, delay)?;
+        }
+        Ok(replay.state_at(replay.len()))
+    }
+}
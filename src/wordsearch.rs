@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+
+/// The eight directions a word can run in, as `(row, col)` steps.
+pub const DIRECTIONS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// A word found in the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMatch {
+    pub start: (usize, usize),
+    pub direction: (i32, i32),
+    /// The cells of each letter in order.
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// How a template was turned before matching: reflected left to right
+/// first if `reflected`, then rotated clockwise by `rotation` quarter
+/// turns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub rotation: u8,
+    pub reflected: bool,
+}
+
+/// A 2D pattern of letters where some cells may be anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    /// Offsets from the top left of the bounding box, in sorted order,
+    /// wildcards left out.
+    cells: Vec<((i32, i32), char)>,
+}
+
+impl Template {
+    /// Build from rows of text where `wildcard` matches any letter.
+    pub fn parse<S: AsRef<str>>(rows: &[S], wildcard: char) -> Self {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| {
+                row.as_ref()
+                    .chars()
+                    .enumerate()
+                    .filter(|&(_, ch)| ch != wildcard)
+                    .map(move |(c, ch)| ((r as i32, c as i32), ch))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Self::normalised(cells)
+    }
+
+    fn normalised(mut cells: Vec<((i32, i32), char)>) -> Self {
+        let top = cells.iter().map(|((r, _), _)| *r).min().unwrap_or(0);
+        let left = cells.iter().map(|((_, c), _)| *c).min().unwrap_or(0);
+        for ((r, c), _) in cells.iter_mut() {
+            *r -= top;
+            *c -= left;
+        }
+        cells.sort();
+        Self { cells }
+    }
+
+    pub fn transformed(&self, orientation: Orientation) -> Self {
+        let cells = self
+            .cells
+            .iter()
+            .map(|&((r, c), ch)| {
+                let (mut r, mut c) = if orientation.reflected {
+                    (r, -c)
+                } else {
+                    (r, c)
+                };
+                for _ in 0..orientation.rotation % 4 {
+                    (r, c) = (c, -r);
+                }
+                ((r, c), ch)
+            })
+            .collect();
+        Self::normalised(cells)
+    }
+
+    /// Every distinct way of turning the template, so symmetric
+    /// templates don't match the same cells more than once.
+    pub fn orientations(&self) -> Vec<(Orientation, Template)> {
+        let mut seen = HashSet::new();
+        let mut found = vec![];
+        for reflected in [false, true] {
+            for rotation in 0..4 {
+                let orientation = Orientation {
+                    rotation,
+                    reflected,
+                };
+                let template = self.transformed(orientation);
+                if seen.insert(template.cells.clone()) {
+                    found.push((orientation, template));
+                }
+            }
+        }
+        found
+    }
+}
+
+/// A template found in the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateMatch {
+    /// Top left of the matched template's bounding box.
+    pub origin: (usize, usize),
+    pub orientation: Orientation,
+    /// The non-wildcard cells matched.
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// A rectangular grid of letters to search.
+#[derive(Debug, Clone)]
+pub struct LetterGrid {
+    width: usize,
+    height: usize,
+    letters: Vec<char>,
+}
+
+impl LetterGrid {
+    pub fn new(rows: &[Vec<char>]) -> Self {
+        Self {
+            width: rows.first().map_or(0, |row| row.len()),
+            height: rows.len(),
+            letters: rows.concat(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell `(r, c)` lands on, wrapping around the edges if `wrap`.
+    fn cell(&self, (r, c): (i32, i32), wrap: bool) -> Option<(usize, usize)> {
+        let (h, w) = (self.height as i32, self.width as i32);
+        if wrap && h > 0 && w > 0 {
+            Some((r.rem_euclid(h) as usize, c.rem_euclid(w) as usize))
+        } else {
+            (r >= 0 && c >= 0 && r < h && c < w).then_some((r as usize, c as usize))
+        }
+    }
+
+    pub fn get(&self, (r, c): (usize, usize)) -> char {
+        self.letters[r * self.width + c]
+    }
+
+    /// Every place `word` reads in a straight line in any of the eight
+    /// directions. With `wrap` the lines carry on from the opposite
+    /// edge. Directions that cover the same cells in the same order,
+    /// as every direction does for a one-letter word, or as wrapping
+    /// does on a grid narrower than the word, are reported once, going
+    /// the first of them in [`DIRECTIONS`].
+    pub fn find_word(&self, word: &str, wrap: bool) -> Vec<WordMatch> {
+        let word = word.chars().collect::<Vec<_>>();
+        let mut found = vec![];
+        if word.is_empty() {
+            return found;
+        }
+        let mut seen = HashSet::new();
+        for r in 0..self.height {
+            for c in 0..self.width {
+                if self.get((r, c)) != word[0] {
+                    continue;
+                }
+                for direction in DIRECTIONS {
+                    let cells = (0..word.len() as i32)
+                        .map(|n| {
+                            self.cell(
+                                (r as i32 + direction.0 * n, c as i32 + direction.1 * n),
+                                wrap,
+                            )
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    let Some(cells) = cells else {
+                        continue;
+                    };
+                    if cells
+                        .iter()
+                        .zip(word.iter())
+                        .all(|(&p, &ch)| self.get(p) == ch)
+                        && seen.insert(cells.clone())
+                    {
+                        found.push(WordMatch {
+                            start: (r, c),
+                            direction,
+                            cells,
+                        });
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Every place `template` fits. With `turn` each distinct rotation
+    /// and reflection is tried as well.
+    pub fn find_template(&self, template: &Template, turn: bool) -> Vec<TemplateMatch> {
+        let orientations = if turn {
+            template.orientations()
+        } else {
+            vec![(Orientation::default(), template.clone())]
+        };
+
+        let mut found = vec![];
+        for r in 0..self.height {
+            for c in 0..self.width {
+                for (orientation, template) in orientations.iter() {
+                    let cells = template
+                        .cells
+                        .iter()
+                        .map(|&((dr, dc), ch)| {
+                            self.cell((r as i32 + dr, c as i32 + dc), false)
+                                .filter(|&p| self.get(p) == ch)
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(cells) = cells {
+                        found.push(TemplateMatch {
+                            origin: (r, c),
+                            orientation: *orientation,
+                            cells,
+                        });
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// The grid with `cells` highlighted in ANSI colour and everything
+    /// else dimmed.
+    pub fn render_highlighted(&self, cells: impl IntoIterator<Item = (usize, usize)>) -> String {
+        let cells: HashSet<(usize, usize)> = cells.into_iter().collect();
+        let mut s = String::new();
+        for r in 0..self.height {
+            for c in 0..self.width {
+                let ch = self.get((r, c));
+                if cells.contains(&(r, c)) {
+                    s.push_str(&format!("\x1b[1;33m{ch}\x1b[0m"));
+                } else {
+                    s.push_str(&format!("\x1b[2m{ch}\x1b[0m"));
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_letter_found_once() {
+        let grid = LetterGrid::new(&[vec!['X', 'A']]);
+        assert_eq!(grid.find_word("X", false).len(), 1);
+        assert_eq!(grid.find_word("X", true).len(), 1);
+        assert_eq!(grid.find_word("XA", false).len(), 1);
+    }
+
+    #[test]
+    fn wrapped_lines_found_once() {
+        let grid = LetterGrid::new(&["SXMA".chars().collect()]);
+        let found = grid.find_word("XMAS", true);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].cells, [(0, 1), (0, 2), (0, 3), (0, 0)]);
+    }
+}